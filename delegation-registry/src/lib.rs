@@ -0,0 +1,143 @@
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, panic_with_error, Address, Env, Vec};
+use storage::Storage;
+mod types;
+pub use crate::types::*;
+
+/// Delegations kept per owner, expired ones are dropped when it is updated
+pub const MAX_DELEGATIONS: u32 = 32;
+
+/// Delegations with this expiration ledger never lapse, as the erc721 approvals
+pub use erc721::NON_EXPIRING;
+
+#[contract]
+pub struct DelegationRegistry;
+
+/// Owners let a delegate, e.g. a hot wallet, act for them on all their tokens,
+/// the tokens of one contract or a single token. Nothing is transferred, the
+/// contracts and services honoring delegations ask `check_delegate`.
+#[contractimpl]
+impl DelegationRegistry {
+    /// Grant `delegate` the rights of `owner` over `scope` until `expiration_ledger`,
+    /// replacing a previous delegation with the same scope
+    pub fn delegate(
+        env: Env,
+        owner: Address,
+        delegate: Address,
+        scope: Scope,
+        expiration_ledger: u32,
+    ) {
+        owner.require_auth();
+        if expiration_ledger < env.ledger().sequence() {
+            panic_with_error!(&env, Error::InvalidExpiration);
+        }
+
+        let mut delegations = active(&env, &owner);
+        if let Some(index) = position(&delegations, &delegate, &scope) {
+            delegations.remove(index);
+        }
+        delegations.push_back(Delegation {
+            delegate: delegate.clone(),
+            scope: scope.clone(),
+            expiration_ledger,
+        });
+        if delegations.len() > MAX_DELEGATIONS {
+            panic_with_error!(&env, Error::TooManyDelegations);
+        }
+        save(&env, &owner, &delegations);
+        Event::Delegated.publish(&env, (owner, delegate, scope, expiration_ledger));
+    }
+
+    pub fn revoke(env: Env, owner: Address, delegate: Address, scope: Scope) {
+        owner.require_auth();
+
+        let mut delegations = active(&env, &owner);
+        let index = position(&delegations, &delegate, &scope)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotDelegated));
+        delegations.remove(index);
+        save(&env, &owner, &delegations);
+        Event::Revoked.publish(&env, (owner, delegate, scope));
+    }
+
+    pub fn revoke_all(env: Env, owner: Address) {
+        owner.require_auth();
+
+        DataKey::Delegations(owner.clone()).remove(&env);
+        Event::RevokedAll.publish(&env, owner);
+    }
+
+    /// Live delegations of `owner`
+    pub fn delegations(env: Env, owner: Address) -> Vec<Delegation> {
+        active(&env, &owner)
+    }
+
+    /// Whether `delegate` acts for `owner` on `token_id` of `contract`, through
+    /// a delegation of the token, of the contract or of everything
+    pub fn check_delegate(
+        env: Env,
+        delegate: Address,
+        owner: Address,
+        contract: Address,
+        token_id: u32,
+    ) -> bool {
+        active(&env, &owner).iter().any(|delegation| {
+            delegation.delegate == delegate
+                && match delegation.scope {
+                    Scope::All => true,
+                    Scope::Contract(c) => c == contract,
+                    Scope::Token(c, id) => c == contract && id == token_id,
+                }
+        })
+    }
+
+    /// Whether `delegate` acts for `owner` on every token of `contract`
+    pub fn check_delegate_for_contract(
+        env: Env,
+        delegate: Address,
+        owner: Address,
+        contract: Address,
+    ) -> bool {
+        active(&env, &owner).iter().any(|delegation| {
+            delegation.delegate == delegate
+                && match delegation.scope {
+                    Scope::All => true,
+                    Scope::Contract(c) => c == contract,
+                    Scope::Token(..) => false,
+                }
+        })
+    }
+}
+
+fn active(env: &Env, owner: &Address) -> Vec<Delegation> {
+    let delegations: Vec<Delegation> = DataKey::Delegations(owner.clone())
+        .get(env)
+        .unwrap_or_else(|| Vec::new(env));
+    let ledger = env.ledger().sequence();
+    let mut live = Vec::new(env);
+    for delegation in delegations.iter() {
+        if delegation.expiration_ledger >= ledger {
+            live.push_back(delegation);
+        }
+    }
+    live
+}
+
+fn position(delegations: &Vec<Delegation>, delegate: &Address, scope: &Scope) -> Option<u32> {
+    delegations
+        .iter()
+        .position(|delegation| delegation.delegate == *delegate && delegation.scope == *scope)
+        .map(|index| index as u32)
+}
+
+fn save(env: &Env, owner: &Address, delegations: &Vec<Delegation>) {
+    let key = DataKey::Delegations(owner.clone());
+    if delegations.is_empty() {
+        key.remove(env);
+    } else {
+        key.set(env, delegations);
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,44 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::testutils::{Address as _, Ledger};
+
+#[test]
+fn delegations() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, DelegationRegistry);
+    let client = DelegationRegistryClient::new(&env, &contract_id);
+
+    let cold = Address::generate(&env);
+    let hot = Address::generate(&env);
+    let collection = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.delegate(&cold, &hot, &Scope::Token(collection.clone(), 7), &100);
+    assert!(client.check_delegate(&hot, &cold, &collection, &7));
+    assert!(!client.check_delegate(&hot, &cold, &collection, &8));
+    assert!(!client.check_delegate_for_contract(&hot, &cold, &collection));
+
+    client.delegate(
+        &cold,
+        &hot,
+        &Scope::Contract(collection.clone()),
+        &NON_EXPIRING,
+    );
+    assert!(client.check_delegate(&hot, &cold, &collection, &8));
+    assert!(client.check_delegate_for_contract(&hot, &cold, &collection));
+    assert!(!client.check_delegate(&hot, &cold, &other, &8));
+    assert_eq!(client.delegations(&cold).len(), 2);
+
+    // The token delegation lapses, the contract one stays
+    env.ledger().with_mut(|ledger| ledger.sequence_number = 101);
+    assert_eq!(client.delegations(&cold).len(), 1);
+    client.revoke(&cold, &hot, &Scope::Contract(collection.clone()));
+    assert!(!client.check_delegate(&hot, &cold, &collection, &7));
+
+    client.delegate(&cold, &hot, &Scope::All, &NON_EXPIRING);
+    assert!(client.check_delegate(&hot, &cold, &other, &1));
+    client.revoke_all(&cold);
+    assert!(client.delegations(&cold).is_empty());
+}
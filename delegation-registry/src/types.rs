@@ -0,0 +1,74 @@
+use soroban_sdk::{contracterror, contracttype, Address, Env, IntoVal, TryFromVal, Val};
+
+/// Tokens a delegation applies to
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Scope {
+    All,
+    Contract(Address),
+    Token(Address, u32),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Delegation {
+    pub delegate: Address,
+    pub scope: Scope,
+    pub expiration_ledger: u32,
+}
+
+#[contracttype]
+pub enum DataKey {
+    Delegations(Address), // persistent
+}
+impl storage::Storage for DataKey {
+    fn get<V: TryFromVal<Env, Val>>(&self, env: &Env) -> Option<V> {
+        storage::Persistent::get(env, self)
+    }
+
+    fn set<V: IntoVal<Env, Val>>(&self, env: &Env, val: &V) {
+        storage::Persistent::set(env, self, val)
+    }
+
+    fn has(&self, env: &Env) -> bool {
+        storage::Persistent::has(env, self)
+    }
+
+    fn extend(&self, env: &Env, min_ledger_to_live: u32) -> &Self {
+        storage::Persistent::extend(env, self, min_ledger_to_live);
+        self
+    }
+
+    fn remove(&self, env: &Env) {
+        storage::Persistent::remove(env, self)
+    }
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug)]
+pub enum Error {
+    InvalidExpiration = 1,
+    TooManyDelegations = 2,
+    NotDelegated = 3,
+}
+
+pub enum Event {
+    Delegated,
+    Revoked,
+    RevokedAll,
+}
+impl Event {
+    fn name(&self) -> &'static str {
+        match self {
+            Event::Delegated => stringify!(Delegated),
+            Event::Revoked => stringify!(Revoked),
+            Event::RevokedAll => stringify!(RevokedAll),
+        }
+    }
+    pub fn publish<D>(&self, env: &Env, value: D)
+    where
+        D: IntoVal<Env, Val>,
+    {
+        env.events().publish((self.name(),), value);
+    }
+}
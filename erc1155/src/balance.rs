@@ -0,0 +1,29 @@
+//! Balances per (owner, id), the entries are removed when they reach zero.
+use crate::{DataKey, Error};
+use soroban_sdk::{panic_with_error, Address, Env};
+use storage::Storage;
+
+pub fn balance(env: &Env, owner: &Address, id: u32) -> u128 {
+    DataKey::Balance(owner.clone(), id).get(env).unwrap_or(0)
+}
+
+pub fn add(env: &Env, owner: &Address, id: u32, amount: u128) {
+    let balance = balance(env, owner, id)
+        .checked_add(amount)
+        .unwrap_or_else(|| panic_with_error!(env, Error::Overflow));
+    if balance != 0 {
+        DataKey::Balance(owner.clone(), id).set(env, &balance);
+    }
+}
+
+pub fn sub(env: &Env, owner: &Address, id: u32, amount: u128) {
+    let key = DataKey::Balance(owner.clone(), id);
+    let balance = balance(env, owner, id)
+        .checked_sub(amount)
+        .unwrap_or_else(|| panic_with_error!(env, Error::InsufficientBalance));
+    if balance == 0 {
+        key.remove(env);
+    } else {
+        key.set(env, &balance);
+    }
+}
@@ -0,0 +1,55 @@
+//! Supply per id and the list of the ids in circulation, an id leaves the
+//! list (swap and pop) when its supply goes back to zero.
+use crate::{DataKeyEnumerable, Error};
+use soroban_sdk::{panic_with_error, Env};
+use storage::Storage;
+
+pub fn total_supply(env: &Env, id: u32) -> u128 {
+    DataKeyEnumerable::TotalSupply(id).get(env).unwrap_or(0)
+}
+
+pub fn token_count(env: &Env) -> u32 {
+    DataKeyEnumerable::TokenCount.get(env).unwrap_or(0)
+}
+
+pub fn add_supply(env: &Env, id: u32, amount: u128) {
+    let supply = total_supply(env, id);
+    if supply == 0 && amount != 0 {
+        let count = token_count(env);
+        DataKeyEnumerable::IndexToken(count).set(env, &id);
+        DataKeyEnumerable::TokenIndex(id).set(env, &count);
+        DataKeyEnumerable::TokenCount.set(env, &(count + 1));
+    }
+    let supply = supply
+        .checked_add(amount)
+        .unwrap_or_else(|| panic_with_error!(env, Error::Overflow));
+    if supply != 0 {
+        DataKeyEnumerable::TotalSupply(id).set(env, &supply);
+    }
+}
+
+#[cfg(feature = "burnable")]
+pub fn sub_supply(env: &Env, id: u32, amount: u128) {
+    let supply = total_supply(env, id)
+        .checked_sub(amount)
+        .unwrap_or_else(|| panic_with_error!(env, Error::InsufficientBalance));
+    if supply != 0 {
+        DataKeyEnumerable::TotalSupply(id).set(env, &supply);
+        return;
+    }
+    if !DataKeyEnumerable::TotalSupply(id).has(env) {
+        return;
+    }
+    DataKeyEnumerable::TotalSupply(id).remove(env);
+
+    let last = token_count(env) - 1;
+    let index: u32 = DataKeyEnumerable::TokenIndex(id).get(env).unwrap();
+    if index != last {
+        let moved: u32 = DataKeyEnumerable::IndexToken(last).get(env).unwrap();
+        DataKeyEnumerable::IndexToken(index).set(env, &moved);
+        DataKeyEnumerable::TokenIndex(moved).set(env, &index);
+    }
+    DataKeyEnumerable::IndexToken(last).remove(env);
+    DataKeyEnumerable::TokenIndex(id).remove(env);
+    DataKeyEnumerable::TokenCount.set(env, &last);
+}
@@ -0,0 +1,6 @@
+use soroban_sdk::{Address, Env, Vec};
+
+pub trait ERC1155Burnable {
+    fn burn(env: Env, spender: Address, from: Address, id: u32, amount: u128);
+    fn burn_batch(env: Env, spender: Address, from: Address, ids: Vec<u32>, amounts: Vec<u128>);
+}
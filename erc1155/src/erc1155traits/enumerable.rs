@@ -0,0 +1,7 @@
+use soroban_sdk::Env;
+pub trait ERC1155Enumerable {
+    fn total_supply(env: Env, id: u32) -> u128;
+    fn exists(env: Env, id: u32) -> bool;
+    fn token_count(env: Env) -> u32;
+    fn token_by_index(env: Env, index: u32) -> u32;
+}
@@ -0,0 +1,31 @@
+use soroban_sdk::{Address, Bytes, Env, Vec};
+pub trait ERC1155 {
+    fn balance_of(env: Env, owner: Address, id: u32) -> u128;
+    fn balance_of_batch(env: Env, owners: Vec<Address>, ids: Vec<u32>) -> Vec<u128>;
+    fn set_approval_for_all(
+        env: Env,
+        owner: Address,
+        operator: Address,
+        approved: bool,
+        expiration_ledger: u32,
+    );
+    fn is_approved_for_all(env: Env, owner: Address, operator: Address) -> bool;
+    fn safe_transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        id: u32,
+        amount: u128,
+        data: Bytes,
+    );
+    fn safe_batch_transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        ids: Vec<u32>,
+        amounts: Vec<u128>,
+        data: Bytes,
+    );
+}
@@ -0,0 +1,6 @@
+use soroban_sdk::{Env, String};
+pub trait ERC1155Metadata {
+    fn name(env: Env) -> String;
+    fn symbol(env: Env) -> String;
+    fn uri(env: Env, id: u32) -> String;
+}
@@ -0,0 +1,4 @@
+pub mod burnable;
+pub mod enumerable;
+pub mod erc1155;
+pub mod metadata;
@@ -0,0 +1,277 @@
+#![no_std]
+
+pub use crate::erc1155traits::burnable::ERC1155Burnable;
+pub use crate::erc1155traits::enumerable::ERC1155Enumerable;
+pub use crate::erc1155traits::erc1155::ERC1155;
+pub use crate::erc1155traits::metadata::ERC1155Metadata;
+pub use crate::types::*;
+use storage::Storage;
+
+#[cfg(feature = "metadata")]
+use soroban_sdk::String;
+use soroban_sdk::{panic_with_error, Address, Bytes, BytesN, Env, Vec};
+
+mod balance;
+#[cfg(feature = "enumerable")]
+mod enumerable;
+mod erc1155traits;
+mod types;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg_attr(test, soroban_sdk::contract)]
+pub struct ERC1155Contract;
+
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC1155 for ERC1155Contract {
+    fn balance_of(env: Env, owner: Address, id: u32) -> u128 {
+        balance::balance(&env, &owner, id)
+    }
+
+    fn balance_of_batch(env: Env, owners: Vec<Address>, ids: Vec<u32>) -> Vec<u128> {
+        if owners.len() != ids.len() {
+            panic_with_error!(&env, Error::LengthMismatch);
+        }
+        let mut balances = Vec::new(&env);
+        for (owner, id) in owners.iter().zip(ids.iter()) {
+            balances.push_back(balance::balance(&env, &owner, id));
+        }
+        balances
+    }
+
+    /// Grant `operator` every token of `owner` until `expiration_ledger` included,
+    /// `u32::MAX` never lapses, as the erc721 operator grants
+    fn set_approval_for_all(
+        env: Env,
+        owner: Address,
+        operator: Address,
+        approved: bool,
+        expiration_ledger: u32,
+    ) {
+        owner.require_auth();
+        let key = DataKey::Operator(owner.clone(), operator.clone());
+        if approved {
+            if expiration_ledger < env.ledger().sequence() {
+                panic_with_error!(&env, Error::InvalidExpiration);
+            }
+            key.set(&env, &expiration_ledger);
+            key.extend(&env, expiration_ledger - env.ledger().sequence());
+        } else {
+            key.remove(&env);
+        }
+        Event::ApprovalForAll.publish(&env, (owner, operator, approved, expiration_ledger));
+    }
+
+    fn is_approved_for_all(env: Env, owner: Address, operator: Address) -> bool {
+        is_operator(&env, &owner, &operator)
+    }
+
+    /// No receiver hook is called, wallet contracts can't answer one for now,
+    /// `data` is only kept for interface compatibility
+    fn safe_transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        id: u32,
+        amount: u128,
+        data: Bytes,
+    ) {
+        let _ = data;
+        check_spender(&env, &spender, &from);
+        balance::sub(&env, &from, id, amount);
+        balance::add(&env, &to, id, amount);
+        Event::TransferSingle.publish(&env, (spender, from, to, id, amount));
+    }
+
+    fn safe_batch_transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        ids: Vec<u32>,
+        amounts: Vec<u128>,
+        data: Bytes,
+    ) {
+        let _ = data;
+        check_spender(&env, &spender, &from);
+        check_lengths(&env, &ids, &amounts);
+        for (id, amount) in ids.iter().zip(amounts.iter()) {
+            balance::sub(&env, &from, id, amount);
+            balance::add(&env, &to, id, amount);
+        }
+        Event::TransferBatch.publish(&env, (spender, from, to, ids, amounts));
+    }
+}
+
+#[cfg(feature = "metadata")]
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC1155Metadata for ERC1155Contract {
+    fn name(env: Env) -> String {
+        DatakeyMetadata::Name.get(&env).unwrap()
+    }
+    fn symbol(env: Env) -> String {
+        DatakeyMetadata::Symbol.get(&env).unwrap()
+    }
+    /// The uri of the token if set, the template otherwise: clients replace
+    /// `{id}` with the id as 64 lowercase hex characters
+    fn uri(env: Env, id: u32) -> String {
+        DatakeyMetadata::TokenUri(id)
+            .get(&env)
+            .or_else(|| DatakeyMetadata::Uri.get(&env))
+            .unwrap_or_else(|| String::from_str(&env, ""))
+    }
+}
+
+#[cfg(feature = "metadata")]
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC1155Contract {
+    /// Set the uri template shared by the tokens without their own uri
+    pub fn set_uri(env: Env, uri: String) {
+        // Authorization should be handled by the caller of the actual implementation
+
+        DatakeyMetadata::Uri.set(&env, &uri);
+        Event::Uri.publish(&env, (uri, Option::<u32>::None));
+    }
+
+    pub fn set_token_uri(env: Env, id: u32, uri: String) {
+        // Authorization should be handled by the caller of the actual implementation
+
+        DatakeyMetadata::TokenUri(id).set(&env, &uri);
+        Event::Uri.publish(&env, (uri, Some(id)));
+    }
+}
+
+#[cfg(feature = "enumerable")]
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC1155Enumerable for ERC1155Contract {
+    fn total_supply(env: Env, id: u32) -> u128 {
+        enumerable::total_supply(&env, id)
+    }
+    fn exists(env: Env, id: u32) -> bool {
+        enumerable::total_supply(&env, id) > 0
+    }
+    fn token_count(env: Env) -> u32 {
+        enumerable::token_count(&env)
+    }
+    fn token_by_index(env: Env, index: u32) -> u32 {
+        DataKeyEnumerable::IndexToken(index)
+            .get(&env)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::OutOfBounds))
+    }
+}
+
+#[cfg(feature = "burnable")]
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC1155Burnable for ERC1155Contract {
+    fn burn(env: Env, spender: Address, from: Address, id: u32, amount: u128) {
+        check_spender(&env, &spender, &from);
+        burn(&env, &from, id, amount);
+        Event::Burn.publish(&env, (from, id, amount));
+    }
+    fn burn_batch(env: Env, spender: Address, from: Address, ids: Vec<u32>, amounts: Vec<u128>) {
+        check_spender(&env, &spender, &from);
+        check_lengths(&env, &ids, &amounts);
+        for (id, amount) in ids.iter().zip(amounts.iter()) {
+            burn(&env, &from, id, amount);
+        }
+        Event::BurnBatch.publish(&env, (from, ids, amounts));
+    }
+}
+
+#[cfg(feature = "metadata")]
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC1155Contract {
+    pub fn initialize(env: Env, admin: Address, name: String, symbol: String, uri: String) {
+        init(&env, &admin);
+        DatakeyMetadata::Name.set(&env, &name);
+        DatakeyMetadata::Symbol.set(&env, &symbol);
+        DatakeyMetadata::Uri.set(&env, &uri);
+    }
+}
+
+// The contract macros don't support feature gated arguments, hence the two versions
+#[cfg(not(feature = "metadata"))]
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC1155Contract {
+    pub fn initialize(env: Env, admin: Address) {
+        init(&env, &admin);
+    }
+}
+
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC1155Contract {
+    pub fn upgrade(env: Env, hash: BytesN<32>) {
+        get_admin(&env).require_auth();
+        env.deployer().update_current_contract_wasm(hash);
+    }
+
+    pub fn mint(env: Env, to: Address, id: u32, amount: u128) {
+        // Authorization should be handled by the caller of the actual implementation
+
+        mint(&env, &to, id, amount);
+        Event::Mint.publish(&env, (to, id, amount));
+    }
+
+    pub fn mint_batch(env: Env, to: Address, ids: Vec<u32>, amounts: Vec<u128>) {
+        // Authorization should be handled by the caller of the actual implementation
+
+        check_lengths(&env, &ids, &amounts);
+        for (id, amount) in ids.iter().zip(amounts.iter()) {
+            mint(&env, &to, id, amount);
+        }
+        Event::MintBatch.publish(&env, (to, ids, amounts));
+    }
+}
+
+/// Store the admin, once
+fn init(env: &Env, admin: &Address) {
+    if Admin::User.has(env) {
+        panic!("Already initialized")
+    }
+    Admin::User.set(env, admin);
+    env.storage().instance().extend_ttl(10000, 10000);
+}
+
+/// Whether `operator` holds a grant of `owner` which has not expired
+fn is_operator(env: &Env, owner: &Address, operator: &Address) -> bool {
+    DataKey::Operator(owner.clone(), operator.clone())
+        .get::<u32>(env)
+        .is_some_and(|expiration_ledger| expiration_ledger >= env.ledger().sequence())
+}
+
+/// Check the spender is the owner or one of its operators
+fn check_spender(env: &Env, spender: &Address, from: &Address) {
+    spender.require_auth();
+    if spender != from && !is_operator(env, from, spender) {
+        panic_with_error!(env, Error::NotAuthorized);
+    }
+}
+
+fn check_lengths(env: &Env, ids: &Vec<u32>, amounts: &Vec<u128>) {
+    if ids.len() != amounts.len() {
+        panic_with_error!(env, Error::LengthMismatch);
+    }
+}
+
+fn mint(env: &Env, to: &Address, id: u32, amount: u128) {
+    balance::add(env, to, id, amount);
+    #[cfg(feature = "enumerable")]
+    enumerable::add_supply(env, id, amount);
+}
+
+#[cfg(feature = "burnable")]
+fn burn(env: &Env, from: &Address, id: u32, amount: u128) {
+    balance::sub(env, from, id, amount);
+    #[cfg(feature = "enumerable")]
+    enumerable::sub_supply(env, id, amount);
+}
+
+pub fn get_admin(env: &Env) -> Address {
+    if let Some(addr) = Admin::User.get(env) {
+        addr
+    } else {
+        panic_with_error!(env, Error::NotAuthorized)
+    }
+}
@@ -0,0 +1,136 @@
+#![cfg(test)]
+extern crate std;
+
+use super::*;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{vec, Address};
+
+/// Register and initialize a fresh contract, whatever the enabled features
+fn setup(env: &Env) -> ERC1155ContractClient<'_> {
+    let contract_id = env.register_contract(None, ERC1155Contract);
+    let client = ERC1155ContractClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    client.initialize(
+        &admin,
+        #[cfg(feature = "metadata")]
+        &String::from_str(env, "Prints"),
+        #[cfg(feature = "metadata")]
+        &String::from_str(env, "PRT"),
+        #[cfg(feature = "metadata")]
+        &String::from_str(env, "ipfs://cid/{id}.json"),
+    );
+    client
+}
+
+#[test]
+fn transfer_test() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let operator = Address::generate(&env);
+    client.mint(&user1, &7, &10);
+    assert_eq!(client.balance_of(&user1, &7), 10);
+    assert_eq!(client.balance_of(&user2, &7), 0);
+
+    client.safe_transfer_from(&user1, &user1, &user2, &7, &4, &Bytes::new(&env));
+    assert_eq!(client.balance_of(&user1, &7), 6);
+    assert_eq!(client.balance_of(&user2, &7), 4);
+
+    client.set_approval_for_all(&user1, &operator, &true, &u32::MAX);
+    assert!(client.is_approved_for_all(&user1, &operator));
+    client.safe_transfer_from(&operator, &user1, &user2, &7, &6, &Bytes::new(&env));
+    assert_eq!(client.balance_of(&user1, &7), 0);
+    assert_eq!(client.balance_of(&user2, &7), 10);
+
+    client.set_approval_for_all(&user1, &operator, &false, &0);
+    assert!(!client.is_approved_for_all(&user1, &operator));
+
+    // Grants lapse after their expiration ledger
+    client.set_approval_for_all(&user1, &operator, &true, &10);
+    env.ledger().with_mut(|l| l.sequence_number = 11);
+    assert!(!client.is_approved_for_all(&user1, &operator));
+}
+
+#[test]
+fn batch_test() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    client.mint_batch(&user1, &vec![&env, 1, 2, 3], &vec![&env, 5, 50, 500]);
+    client.safe_batch_transfer_from(
+        &user1,
+        &user1,
+        &user2,
+        &vec![&env, 1, 3],
+        &vec![&env, 5, 100],
+        &Bytes::new(&env),
+    );
+    assert_eq!(
+        client.balance_of_batch(
+            &vec![
+                &env,
+                user1.clone(),
+                user1.clone(),
+                user1,
+                user2.clone(),
+                user2
+            ],
+            &vec![&env, 1, 2, 3, 1, 3]
+        ),
+        vec![&env, 0, 50, 400, 5, 100]
+    );
+}
+
+#[test]
+#[cfg(all(feature = "enumerable", feature = "burnable"))]
+fn supply_test() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    client.mint_batch(&user1, &vec![&env, 1, 2, 3], &vec![&env, 5, 50, 500]);
+    client.mint(&user2, &2, &25);
+    assert_eq!(client.total_supply(&2), 75);
+    assert_eq!(client.token_count(), 3);
+
+    // The last id takes the place of the burned out one
+    client.burn(&user1, &user1, &1, &5);
+    assert!(!client.exists(&1));
+    assert_eq!(client.token_count(), 2);
+    assert_eq!(client.token_by_index(&0), 3);
+    assert_eq!(client.token_by_index(&1), 2);
+
+    client.burn_batch(&user1, &user1, &vec![&env, 2, 3], &vec![&env, 50, 1]);
+    assert_eq!(client.total_supply(&2), 25);
+    assert_eq!(client.total_supply(&3), 499);
+    assert_eq!(client.balance_of(&user1, &2), 0);
+}
+
+#[test]
+#[cfg(feature = "metadata")]
+fn uri_test() {
+    let env = Env::default();
+    let client = setup(&env);
+
+    assert_eq!(client.name(), String::from_str(&env, "Prints"));
+    assert_eq!(
+        client.uri(&1),
+        String::from_str(&env, "ipfs://cid/{id}.json")
+    );
+
+    client.set_token_uri(&1, &String::from_str(&env, "ar://print-1"));
+    assert_eq!(client.uri(&1), String::from_str(&env, "ar://print-1"));
+    assert_eq!(
+        client.uri(&2),
+        String::from_str(&env, "ipfs://cid/{id}.json")
+    );
+}
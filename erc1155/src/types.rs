@@ -0,0 +1,190 @@
+use soroban_sdk::{contracterror, contracttype, Address, Env, IntoVal, TryFromVal, Val};
+
+#[contracttype]
+pub enum Admin {
+    User,
+}
+impl storage::Storage for Admin {
+    fn get<V: TryFromVal<Env, Val>>(&self, env: &Env) -> Option<V> {
+        storage::Persistent::get(env, self)
+    }
+
+    fn set<V: IntoVal<Env, Val>>(&self, env: &Env, val: &V) {
+        storage::Persistent::set(env, self, val)
+    }
+
+    fn has(&self, env: &Env) -> bool {
+        storage::Persistent::has(env, self)
+    }
+
+    fn extend(&self, env: &Env, min_ledger_to_live: u32) -> &Self {
+        storage::Persistent::extend(env, self, min_ledger_to_live);
+        self
+    }
+
+    fn remove(&self, env: &Env) {
+        storage::Persistent::remove(env, self)
+    }
+}
+
+#[contracttype]
+pub enum DataKey {
+    Balance(Address, u32),      // persistent
+    Operator(Address, Address), // persistent
+}
+impl storage::Storage for DataKey {
+    fn get<V: TryFromVal<Env, Val>>(&self, env: &Env) -> Option<V> {
+        storage::Persistent::get(env, self)
+    }
+
+    fn set<V: IntoVal<Env, Val>>(&self, env: &Env, val: &V) {
+        storage::Persistent::set(env, self, val)
+    }
+
+    fn has(&self, env: &Env) -> bool {
+        storage::Persistent::has(env, self)
+    }
+
+    fn extend(&self, env: &Env, min_ledger_to_live: u32) -> &Self {
+        storage::Persistent::extend(env, self, min_ledger_to_live);
+        self
+    }
+
+    fn remove(&self, env: &Env) {
+        storage::Persistent::remove(env, self)
+    }
+}
+
+#[contracttype]
+pub enum DatakeyMetadata {
+    Name,          // instance
+    Symbol,        // instance
+    Uri,           // instance
+    TokenUri(u32), // persistent
+}
+impl storage::Storage for DatakeyMetadata {
+    fn get<V: TryFromVal<Env, Val>>(&self, env: &Env) -> Option<V> {
+        match self {
+            DatakeyMetadata::TokenUri(_) => storage::Persistent::get(env, self),
+            _ => storage::Instance::get(env, self),
+        }
+    }
+
+    fn set<V: IntoVal<Env, Val>>(&self, env: &Env, val: &V) {
+        match self {
+            DatakeyMetadata::TokenUri(_) => storage::Persistent::set(env, self, val),
+            _ => storage::Instance::set(env, self, val),
+        }
+    }
+
+    fn has(&self, env: &Env) -> bool {
+        match self {
+            DatakeyMetadata::TokenUri(_) => storage::Persistent::has(env, self),
+            _ => storage::Instance::has(env, self),
+        }
+    }
+
+    fn extend(&self, env: &Env, min_ledger_to_live: u32) -> &Self {
+        match self {
+            DatakeyMetadata::TokenUri(_) => {
+                storage::Persistent::extend(env, self, min_ledger_to_live)
+            }
+            _ => storage::Instance::extend(env, min_ledger_to_live),
+        }
+        self
+    }
+
+    fn remove(&self, env: &Env) {
+        match self {
+            DatakeyMetadata::TokenUri(_) => storage::Persistent::remove(env, self),
+            _ => storage::Instance::remove(env, self),
+        }
+    }
+}
+
+#[contracttype]
+pub enum DataKeyEnumerable {
+    TokenCount,       // instance
+    TotalSupply(u32), // persistent
+    IndexToken(u32),  // persistent
+    TokenIndex(u32),  // persistent
+}
+impl storage::Storage for DataKeyEnumerable {
+    fn get<V: TryFromVal<Env, Val>>(&self, env: &Env) -> Option<V> {
+        match self {
+            DataKeyEnumerable::TokenCount => storage::Instance::get(env, self),
+            _ => storage::Persistent::get(env, self),
+        }
+    }
+
+    fn set<V: IntoVal<Env, Val>>(&self, env: &Env, val: &V) {
+        match self {
+            DataKeyEnumerable::TokenCount => storage::Instance::set(env, self, val),
+            _ => storage::Persistent::set(env, self, val),
+        }
+    }
+
+    fn has(&self, env: &Env) -> bool {
+        match self {
+            DataKeyEnumerable::TokenCount => storage::Instance::has(env, self),
+            _ => storage::Persistent::has(env, self),
+        }
+    }
+
+    fn extend(&self, env: &Env, min_ledger_to_live: u32) -> &Self {
+        match self {
+            DataKeyEnumerable::TokenCount => storage::Instance::extend(env, min_ledger_to_live),
+            _ => storage::Persistent::extend(env, self, min_ledger_to_live),
+        }
+        self
+    }
+
+    fn remove(&self, env: &Env) {
+        match self {
+            DataKeyEnumerable::TokenCount => storage::Instance::remove(env, self),
+            _ => storage::Persistent::remove(env, self),
+        }
+    }
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug)]
+pub enum Error {
+    InsufficientBalance = 1,
+    NotAuthorized = 2,
+    LengthMismatch = 3,
+    OutOfBounds = 4,
+    Overflow = 5,
+    InvalidExpiration = 6,
+}
+
+pub enum Event {
+    Mint,
+    MintBatch,
+    TransferSingle,
+    TransferBatch,
+    Burn,
+    BurnBatch,
+    ApprovalForAll,
+    Uri,
+}
+impl Event {
+    fn name(&self) -> &'static str {
+        match self {
+            Event::Mint => stringify!(Mint),
+            Event::MintBatch => stringify!(MintBatch),
+            Event::TransferSingle => stringify!(TransferSingle),
+            Event::TransferBatch => stringify!(TransferBatch),
+            Event::Burn => stringify!(Burn),
+            Event::BurnBatch => stringify!(BurnBatch),
+            Event::ApprovalForAll => stringify!(ApprovalForAll),
+            Event::Uri => stringify!(URI),
+        }
+    }
+    pub fn publish<D>(&self, env: &Env, value: D)
+    where
+        D: IntoVal<Env, Val>,
+    {
+        env.events().publish((self.name(),), value);
+    }
+}
@@ -1,10 +1,9 @@
 #![no_std]
 
-use erc721::{DatakeyMetadata, ERC721Metadata, ERC721};
-use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, String};
-
-#[contracttype]
-pub struct Id();
+use erc721::{
+    AirdropSummary, ERC721Introspection, ERC721Metadata, MintVoucher, TokenMetadata, ERC721,
+};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol, Vec};
 
 #[contract]
 pub struct MyNFTCollection;
@@ -15,8 +14,8 @@ pub struct MyNFTCollection;
 #[contractimpl]
 impl MyNFTCollection {
     pub fn initialize(env: Env, admin: Address) {
-        let name = String::from_slice(&env, "Non-Fungible Token");
-        let sym = String::from_slice(&env, "NFT");
+        let name = String::from_str(&env, "Non-Fungible Token");
+        let sym = String::from_str(&env, "NFT");
         erc721::ERC721Contract::initialize(env, admin, name, sym);
     }
 
@@ -28,19 +27,62 @@ impl MyNFTCollection {
         // Check ownly the admin can mint
         erc721::get_admin(&env).require_auth();
 
-        // Get and increment token id
-        let token_id = env.storage().instance().get(&Id()).unwrap_or(0);
-        env.storage().instance().set(&Id(), &(token_id + 1));
+        // Shared with the vouchers not naming a token id
+        let token_id = erc721::next_token_id(&env);
 
         // set the uri for the token id
-        env.storage()
-            .persistent()
-            .set(&DatakeyMetadata::Uri(token_id), &uri);
+        erc721::ERC721Contract::set_token_uri(env.clone(), token_id, uri);
 
         // Mint
         erc721::ERC721Contract::mint(env.clone(), to.clone(), token_id)
     }
 
+    /// Mint reserved tokens, call again from `next` until the whole list is done
+    pub fn airdrop(env: Env, recipients: Vec<(Address, u32)>) -> AirdropSummary {
+        erc721::get_admin(&env).require_auth();
+        erc721::ERC721Contract::airdrop(env, recipients)
+    }
+
+    /// Let the holder of this ed25519 key sign mint vouchers, the admin key can
+    /// stay offline
+    pub fn set_voucher_signer(env: Env, public_key: BytesN<32>) {
+        erc721::get_admin(&env).require_auth();
+        erc721::ERC721Contract::set_voucher_signer(env, public_key)
+    }
+
+    pub fn voucher_signer(env: Env) -> Option<BytesN<32>> {
+        erc721::ERC721Contract::voucher_signer(env)
+    }
+
+    pub fn redeem_voucher(
+        env: Env,
+        redeemer: Address,
+        voucher: MintVoucher,
+        signature: BytesN<64>,
+    ) -> u32 {
+        erc721::ERC721Contract::redeem_voucher(env, redeemer, voucher, signature)
+    }
+
+    pub fn set_base_uri(env: Env, uri: String) {
+        erc721::get_admin(&env).require_auth();
+        erc721::ERC721Contract::set_base_uri(env, uri)
+    }
+
+    pub fn set_token_uri(env: Env, token_id: u32, uri: String) {
+        erc721::get_admin(&env).require_auth();
+        erc721::ERC721Contract::set_token_uri(env, token_id, uri)
+    }
+
+    pub fn set_token_metadata(env: Env, token_id: u32, metadata: TokenMetadata) {
+        erc721::get_admin(&env).require_auth();
+        erc721::ERC721Contract::set_token_metadata(env, token_id, metadata)
+    }
+
+    pub fn set_contract_uri(env: Env, uri: String) {
+        erc721::get_admin(&env).require_auth();
+        erc721::ERC721Contract::set_contract_uri(env, uri)
+    }
+
     pub fn balance_of(env: Env, owner: Address) -> u32 {
         erc721::ERC721Contract::balance_of(env, owner)
     }
@@ -85,6 +127,22 @@ impl MyNFTCollection {
         erc721::ERC721Contract::is_approval_for_all(env, owner, operator)
     }
 
+    pub fn approval_expiration(env: Env, token_id: u32) -> Option<u32> {
+        erc721::ERC721Contract::approval_expiration(env, token_id)
+    }
+
+    pub fn operator_expiration(env: Env, owner: Address, operator: Address) -> Option<u32> {
+        erc721::ERC721Contract::operator_expiration(env, owner, operator)
+    }
+
+    pub fn revoke_all_operators(env: Env, owner: Address) {
+        erc721::ERC721Contract::revoke_all_operators(env, owner)
+    }
+
+    pub fn owner_of(env: Env, token_id: u32) -> Address {
+        erc721::ERC721Contract::owner_of(env, token_id)
+    }
+
     pub fn name(env: Env) -> String {
         erc721::ERC721Contract::name(env)
     }
@@ -96,4 +154,15 @@ impl MyNFTCollection {
     pub fn token_uri(env: Env, token_id: u32) -> String {
         erc721::ERC721Contract::token_uri(env, token_id)
     }
+
+    pub fn contract_uri(env: Env) -> String {
+        erc721::ERC721Contract::contract_uri(env)
+    }
+
+    pub fn supports_interface(env: Env, interface_id: Symbol) -> bool {
+        erc721::ERC721Contract::supports_interface(env, interface_id)
+    }
 }
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,69 @@
+#![cfg(test)]
+extern crate std;
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+#[test]
+fn conformance() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, MyNFTCollection);
+    let client = MyNFTCollectionClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    // Token ids are allocated in sequence from 0
+    let next_id = core::cell::Cell::new(0);
+    let mint = |to: &Address| {
+        let token_id = next_id.get();
+        next_id.set(token_id + 1);
+        client
+            .mock_all_auths()
+            .mint(to, &String::from_str(&env, "ipfs://cid"));
+        token_id
+    };
+    erc721_testkit::Suite::new(&env, &contract_id, &mint).run();
+}
+
+#[test]
+fn redeem_voucher() {
+    use ed25519_dalek::{Signer, SigningKey};
+    use erc721::Recipient;
+    use soroban_sdk::{token, xdr::ToXdr};
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, MyNFTCollection);
+    let client = MyNFTCollectionClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let key = SigningKey::from_bytes(&[3; 32]);
+    client.set_voucher_signer(&BytesN::from_array(&env, &key.verifying_key().to_bytes()));
+
+    let asset = env.register_stellar_asset_contract(Address::generate(&env));
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &asset).mint(&buyer, &50);
+
+    client.mint(&buyer, &String::from_str(&env, "ipfs://cid/0"));
+    let voucher = MintVoucher {
+        recipient: Recipient::Anyone,
+        token_id: None,
+        uri: String::from_str(&env, "ipfs://cid/1"),
+        price: 50,
+        asset: asset.clone(),
+        expiration_ledger: 1000,
+        nonce: 0,
+    };
+    // The signer signs the XDR of (collection, voucher)
+    let message = (contract_id.clone(), voucher.clone()).to_xdr(&env);
+    let message: std::vec::Vec<u8> = message.iter().collect();
+    let signature = BytesN::from_array(&env, &key.sign(&message).to_bytes());
+
+    assert_eq!(client.redeem_voucher(&buyer, &voucher, &signature), 1);
+    assert_eq!(client.owner_of(&1), buyer);
+    assert_eq!(token::Client::new(&env, &asset).balance(&admin), 50);
+    client.mint(&buyer, &String::from_str(&env, "ipfs://cid/2"));
+    assert_eq!(client.owner_of(&2), buyer);
+}
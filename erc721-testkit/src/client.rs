@@ -0,0 +1,44 @@
+//! Clients for the functions the suite calls, any contract exposing the
+//! erc721 interface under the same names can be tested.
+use soroban_sdk::{contractclient, Address, Env, Symbol};
+
+#[allow(dead_code)]
+#[contractclient(name = "ERC721Client")]
+pub trait ERC721Interface {
+    fn balance_of(env: Env, owner: Address) -> u32;
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, token_id: u32);
+    fn approve(
+        env: Env,
+        caller: Address,
+        operator: Option<Address>,
+        token_id: u32,
+        expiration_ledger: u32,
+    );
+    fn set_approval_for_all(
+        env: Env,
+        caller: Address,
+        owner: Address,
+        operator: Address,
+        approved: bool,
+        expiration_ledger: u32,
+    );
+    fn get_approved(env: Env, token_id: u32) -> Option<Address>;
+    fn is_approval_for_all(env: Env, owner: Address, operator: Address) -> bool;
+    fn owner_of(env: Env, token_id: u32) -> Address;
+    fn revoke_all_operators(env: Env, owner: Address);
+    fn supports_interface(env: Env, interface_id: Symbol) -> bool;
+}
+
+#[allow(dead_code)]
+#[contractclient(name = "BurnableClient")]
+pub trait BurnableInterface {
+    fn burn(env: Env, caller: Address, token_id: u32);
+}
+
+#[allow(dead_code)]
+#[contractclient(name = "EnumerableClient")]
+pub trait EnumerableInterface {
+    fn total_supply(env: Env) -> u32;
+    fn token_by_index(env: Env, index: u32) -> u32;
+    fn token_of_owner_by_index(env: Env, owner: Address, index: u32) -> u32;
+}
@@ -0,0 +1,339 @@
+#![no_std]
+//! Conformance test kit for the contracts built on the erc721 crate.
+//!
+//! The suite runs against any registered contract id, the collection only
+//! provides a closure minting a new token to a given address:
+//!
+//! ```ignore
+//! let mint = |to: &Address| client.mint(to, &uri);
+//! erc721_testkit::Suite::new(&env, &contract_id, &mint)
+//!     .burnable()
+//!     .enumerable()
+//!     .run();
+//! ```
+//!
+//! All the authorizations are mocked, the suite then checks the auth tree
+//! recorded for each call.
+#![cfg(feature = "testutils")]
+extern crate std;
+
+mod client;
+
+pub use crate::client::{BurnableClient, ERC721Client, EnumerableClient};
+
+use erc721::NON_EXPIRING;
+use soroban_sdk::testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Events};
+use soroban_sdk::{vec, Address, Env, IntoVal, Symbol, Val, Vec};
+
+pub struct Suite<'a> {
+    env: &'a Env,
+    contract_id: Address,
+    mint: &'a dyn Fn(&Address) -> u32,
+    burnable: bool,
+    enumerable: bool,
+}
+
+impl<'a> Suite<'a> {
+    /// `mint` must mint a new token to the given address and return its id
+    pub fn new(env: &'a Env, contract_id: &Address, mint: &'a dyn Fn(&Address) -> u32) -> Self {
+        Suite {
+            env,
+            contract_id: contract_id.clone(),
+            mint,
+            burnable: false,
+            enumerable: false,
+        }
+    }
+
+    /// Also test the `burn` function
+    pub fn burnable(mut self) -> Self {
+        self.burnable = true;
+        self
+    }
+
+    /// Also test the enumerable indexes
+    pub fn enumerable(mut self) -> Self {
+        self.enumerable = true;
+        self
+    }
+
+    /// Run the whole battery
+    pub fn run(&self) {
+        self.env.mock_all_auths();
+        self.introspection_test();
+        self.mint_test();
+        self.transfer_test();
+        self.approval_test();
+        self.operator_test();
+        self.events_test();
+        self.auth_test();
+        if self.burnable {
+            self.burn_test();
+        }
+        if self.enumerable {
+            self.enumerable_test();
+        }
+    }
+
+    pub fn introspection_test(&self) {
+        let client = self.client();
+        let supports = |name| client.supports_interface(&Symbol::new(self.env, name));
+        assert!(supports("erc721"));
+        // The tested extensions must be declared, the reverse depends on the
+        // features the erc721 crate is built with
+        assert!(!self.burnable || supports("burnable"));
+        assert!(!self.enumerable || supports("enumerable"));
+    }
+
+    pub fn mint_test(&self) {
+        let client = self.client();
+        let owner = Address::generate(self.env);
+
+        let token_id = (self.mint)(&owner);
+        assert_eq!(client.owner_of(&token_id), owner);
+        assert_eq!(client.balance_of(&owner), 1);
+        assert_eq!(client.get_approved(&token_id), None);
+
+        let other_id = (self.mint)(&owner);
+        assert_ne!(token_id, other_id, "mint must return a new token id");
+        assert_eq!(client.balance_of(&owner), 2);
+    }
+
+    pub fn transfer_test(&self) {
+        let client = self.client();
+        let owner = Address::generate(self.env);
+        let receiver = Address::generate(self.env);
+        let token_id = (self.mint)(&owner);
+
+        client.transfer_from(&owner, &owner, &receiver, &token_id);
+        assert_eq!(client.owner_of(&token_id), receiver);
+        assert_eq!(client.balance_of(&owner), 0);
+        assert_eq!(client.balance_of(&receiver), 1);
+
+        // Transferring to oneself changes nothing
+        client.transfer_from(&receiver, &receiver, &receiver, &token_id);
+        assert_eq!(client.owner_of(&token_id), receiver);
+        assert_eq!(client.balance_of(&receiver), 1);
+
+        client.transfer_from(&receiver, &receiver, &owner, &token_id);
+        assert_eq!(client.owner_of(&token_id), owner);
+        assert_eq!(client.balance_of(&owner), 1);
+        assert_eq!(client.balance_of(&receiver), 0);
+    }
+
+    pub fn approval_test(&self) {
+        let client = self.client();
+        let owner = Address::generate(self.env);
+        let spender = Address::generate(self.env);
+        let receiver = Address::generate(self.env);
+        let token_id = (self.mint)(&owner);
+
+        client.approve(&owner, &Some(spender.clone()), &token_id, &NON_EXPIRING);
+        assert_eq!(client.get_approved(&token_id), Some(spender.clone()));
+
+        // Clearing the approval
+        client.approve(&owner, &None, &token_id, &NON_EXPIRING);
+        assert_eq!(client.get_approved(&token_id), None);
+
+        // The approved address can transfer, and the approval does not survive it
+        client.approve(&owner, &Some(spender.clone()), &token_id, &NON_EXPIRING);
+        client.transfer_from(&spender, &owner, &receiver, &token_id);
+        assert_eq!(client.owner_of(&token_id), receiver);
+        assert_eq!(client.get_approved(&token_id), None);
+    }
+
+    pub fn operator_test(&self) {
+        let client = self.client();
+        let owner = Address::generate(self.env);
+        let operator = Address::generate(self.env);
+        let spender = Address::generate(self.env);
+        let receiver = Address::generate(self.env);
+        let token_id = (self.mint)(&owner);
+        let other_id = (self.mint)(&owner);
+
+        client.set_approval_for_all(&owner, &owner, &operator, &true, &NON_EXPIRING);
+        assert!(client.is_approval_for_all(&owner, &operator));
+        assert!(!client.is_approval_for_all(&operator, &owner));
+
+        // An operator can transfer and approve on behalf of the owner
+        client.transfer_from(&operator, &owner, &receiver, &token_id);
+        assert_eq!(client.owner_of(&token_id), receiver);
+        client.approve(&operator, &Some(spender.clone()), &other_id, &NON_EXPIRING);
+        assert_eq!(client.get_approved(&other_id), Some(spender));
+
+        // Operators are not tied to a token, they don't follow it
+        assert!(!client.is_approval_for_all(&receiver, &operator));
+
+        client.set_approval_for_all(&owner, &owner, &operator, &false, &NON_EXPIRING);
+        assert!(!client.is_approval_for_all(&owner, &operator));
+
+        client.set_approval_for_all(&owner, &owner, &operator, &true, &NON_EXPIRING);
+        client.revoke_all_operators(&owner);
+        assert!(!client.is_approval_for_all(&owner, &operator));
+    }
+
+    pub fn burn_test(&self) {
+        let client = self.client();
+        let burnable = BurnableClient::new(self.env, &self.contract_id);
+        let owner = Address::generate(self.env);
+        let operator = Address::generate(self.env);
+        let token_id = (self.mint)(&owner);
+        let other_id = (self.mint)(&owner);
+
+        burnable.burn(&owner, &token_id);
+        assert_eq!(client.balance_of(&owner), 1);
+        self.assert_event("Burn", token_id.into_val(self.env));
+
+        client.set_approval_for_all(&owner, &owner, &operator, &true, &NON_EXPIRING);
+        burnable.burn(&operator, &other_id);
+        assert_eq!(client.balance_of(&owner), 0);
+    }
+
+    pub fn enumerable_test(&self) {
+        let client = self.client();
+        let enumerable = EnumerableClient::new(self.env, &self.contract_id);
+        let owner = Address::generate(self.env);
+        let receiver = Address::generate(self.env);
+
+        let supply = enumerable.total_supply();
+        let ids = [
+            (self.mint)(&owner),
+            (self.mint)(&owner),
+            (self.mint)(&owner),
+        ];
+        assert_eq!(enumerable.total_supply(), supply + 3);
+
+        client.transfer_from(&owner, &owner, &receiver, &ids[1]);
+        self.assert_owner_index(&owner, &[ids[0], ids[2]]);
+        self.assert_owner_index(&receiver, &[ids[1]]);
+
+        if self.burnable {
+            BurnableClient::new(self.env, &self.contract_id).burn(&owner, &ids[0]);
+            assert_eq!(enumerable.total_supply(), supply + 2);
+            self.assert_owner_index(&owner, &[ids[2]]);
+        }
+
+        // The global index lists each token once
+        let total = enumerable.total_supply();
+        let mut all = std::vec::Vec::new();
+        for index in 0..total {
+            let token_id = enumerable.token_by_index(&index);
+            assert!(!all.contains(&token_id), "token {token_id} indexed twice");
+            all.push(token_id);
+        }
+        assert!(all.contains(&ids[1]) && all.contains(&ids[2]));
+    }
+
+    pub fn events_test(&self) {
+        let client = self.client();
+        let owner = Address::generate(self.env);
+        let receiver = Address::generate(self.env);
+        let spender = Address::generate(self.env);
+
+        let token_id = (self.mint)(&owner);
+        self.assert_event("Mint", (owner.clone(), token_id).into_val(self.env));
+
+        client.approve(&owner, &Some(spender.clone()), &token_id, &NON_EXPIRING);
+        self.assert_event(
+            "Approve",
+            (token_id, Some(spender), NON_EXPIRING).into_val(self.env),
+        );
+
+        client.transfer_from(&owner, &owner, &receiver, &token_id);
+        self.assert_event("Transfer", (owner, receiver, token_id).into_val(self.env));
+    }
+
+    pub fn auth_test(&self) {
+        let client = self.client();
+        let owner = Address::generate(self.env);
+        let operator = Address::generate(self.env);
+        let receiver = Address::generate(self.env);
+        let token_id = (self.mint)(&owner);
+
+        client.approve(&owner, &Some(operator.clone()), &token_id, &NON_EXPIRING);
+        self.assert_auth(
+            &owner,
+            "approve",
+            (
+                owner.clone(),
+                Some(operator.clone()),
+                token_id,
+                NON_EXPIRING,
+            )
+                .into_val(self.env),
+        );
+
+        client.set_approval_for_all(&owner, &owner, &operator, &true, &NON_EXPIRING);
+        self.assert_auth(
+            &owner,
+            "set_approval_for_all",
+            (
+                owner.clone(),
+                owner.clone(),
+                operator.clone(),
+                true,
+                NON_EXPIRING,
+            )
+                .into_val(self.env),
+        );
+
+        // The spender signs the transfer, not the owner
+        client.transfer_from(&operator, &owner, &receiver, &token_id);
+        self.assert_auth(
+            &operator,
+            "transfer_from",
+            (operator.clone(), owner, receiver, token_id).into_val(self.env),
+        );
+    }
+
+    fn client(&self) -> ERC721Client<'a> {
+        ERC721Client::new(self.env, &self.contract_id)
+    }
+
+    /// Check the owner index holds exactly `expected`, in any order
+    fn assert_owner_index(&self, owner: &Address, expected: &[u32]) {
+        let enumerable = EnumerableClient::new(self.env, &self.contract_id);
+        assert_eq!(self.client().balance_of(owner), expected.len() as u32);
+        for index in 0..expected.len() as u32 {
+            let token_id = enumerable.token_of_owner_by_index(owner, &index);
+            assert!(expected.contains(&token_id), "token {token_id} not owned");
+        }
+    }
+
+    /// Check the last event of the contract has the given name and data
+    fn assert_event(&self, name: &str, data: Val) {
+        let (topics, value) = self
+            .env
+            .events()
+            .all()
+            .iter()
+            .filter(|(contract_id, _, _)| contract_id == &self.contract_id)
+            .map(|(_, topics, value)| (topics, value))
+            .last()
+            .unwrap_or_else(|| panic!("no event, expected {name}"));
+        // The erc721 crate publishes the event name as the only topic
+        let name_topic: Vec<Val> = vec![self.env, name.into_val(self.env)];
+        assert_eq!(topics, name_topic, "expected a {name} event");
+        let value: Vec<Val> = vec![self.env, value];
+        let data: Vec<Val> = vec![self.env, data];
+        assert_eq!(value, data, "unexpected {name} data");
+    }
+
+    /// Check the last call was authorized by `signer` only, without sub invocations
+    fn assert_auth(&self, signer: &Address, function: &str, args: Vec<Val>) {
+        assert_eq!(
+            self.env.auths(),
+            std::vec![(
+                signer.clone(),
+                AuthorizedInvocation {
+                    function: AuthorizedFunction::Contract((
+                        self.contract_id.clone(),
+                        Symbol::new(self.env, function),
+                        args,
+                    )),
+                    sub_invocations: std::vec![],
+                }
+            )]
+        );
+    }
+}
@@ -0,0 +1,75 @@
+//! Token approvals and operator grants.
+//!
+//! Both are kept in persistent storage with an explicit expiration ledger, so
+//! they are never silently dropped and can be queried until they lapse.
+use crate::{Approval, DataKey, Error, OperatorGrant};
+use soroban_sdk::{panic_with_error, Address, Env};
+use storage::Storage;
+
+/// Operator grants with this expiration ledger never lapse
+pub const NON_EXPIRING: u32 = u32::MAX;
+
+/// Live approval of a token
+pub fn approval(env: &Env, token_id: u32) -> Option<Approval> {
+    DataKey::Approved(token_id)
+        .get::<Approval>(env)
+        .filter(|approval| approval.expiration_ledger >= env.ledger().sequence())
+}
+
+pub fn approved(env: &Env, token_id: u32) -> Option<Address> {
+    approval(env, token_id).map(|approval| approval.address)
+}
+
+pub fn set_approval(env: &Env, token_id: u32, address: Address, expiration_ledger: u32) {
+    check_expiration(env, expiration_ledger);
+    let key = DataKey::Approved(token_id);
+    key.set(
+        env,
+        &Approval {
+            address,
+            expiration_ledger,
+        },
+    );
+    key.extend(env, expiration_ledger - env.ledger().sequence());
+}
+
+/// Live grant of an operator, grants issued before the last revocation are ignored
+pub fn operator_grant(env: &Env, owner: &Address, operator: &Address) -> Option<OperatorGrant> {
+    DataKey::Operator(owner.clone(), operator.clone())
+        .get::<OperatorGrant>(env)
+        .filter(|grant| {
+            grant.epoch == epoch(env, owner) && grant.expiration_ledger >= env.ledger().sequence()
+        })
+}
+
+pub fn is_operator(env: &Env, owner: &Address, operator: &Address) -> bool {
+    operator_grant(env, owner, operator).is_some()
+}
+
+pub fn set_operator(env: &Env, owner: &Address, operator: &Address, expiration_ledger: u32) {
+    check_expiration(env, expiration_ledger);
+    let key = DataKey::Operator(owner.clone(), operator.clone());
+    key.set(
+        env,
+        &OperatorGrant {
+            epoch: epoch(env, owner),
+            expiration_ledger,
+        },
+    );
+    key.extend(env, expiration_ledger - env.ledger().sequence());
+}
+
+/// Invalidate every operator grant of the owner at once
+pub fn revoke_all(env: &Env, owner: &Address) {
+    DataKey::OperatorEpoch(owner.clone()).set(env, &(epoch(env, owner) + 1));
+}
+
+fn epoch(env: &Env, owner: &Address) -> u32 {
+    DataKey::OperatorEpoch(owner.clone()).get(env).unwrap_or(0)
+}
+
+fn check_expiration(env: &Env, expiration_ledger: u32) {
+    if expiration_ledger < env.ledger().sequence() {
+        panic_with_error!(env, Error::InvalidExpiration);
+    }
+}
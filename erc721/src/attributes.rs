@@ -0,0 +1,45 @@
+//! Typed key/value attributes stored on-chain, per token and for the whole
+//! collection (district, colour theme, edition...).
+//!
+//! They are managed by the metadata role, the admin until another address is
+//! given the role.
+use crate::{get_admin, AttributeValue, DataKeyAttributes, Error};
+use soroban_sdk::{panic_with_error, Address, Env, Map, Symbol};
+use storage::Storage;
+
+/// Most attributes a token, or the collection, can hold
+pub const MAX_ATTRIBUTES: u32 = 32;
+
+/// Most tokens read at once by `attributes_of`
+pub const MAX_BATCH_SIZE: u32 = 100;
+
+pub fn metadata_role(env: &Env) -> Address {
+    DataKeyAttributes::MetadataRole
+        .get(env)
+        .unwrap_or_else(|| get_admin(env))
+}
+
+pub fn get(env: &Env, key: &DataKeyAttributes) -> Map<Symbol, AttributeValue> {
+    key.get(env).unwrap_or_else(|| Map::new(env))
+}
+
+/// Set or remove (`None`) one attribute under `key`
+pub fn set(env: &Env, key: &DataKeyAttributes, name: Symbol, value: Option<AttributeValue>) {
+    let mut attributes = get(env, key);
+    match value {
+        Some(value) => {
+            attributes.set(name, value);
+            if attributes.len() > MAX_ATTRIBUTES {
+                panic_with_error!(env, Error::TooManyAttributes);
+            }
+        }
+        None => {
+            attributes.remove(name);
+        }
+    }
+    if attributes.is_empty() {
+        key.remove(env);
+    } else {
+        key.set(env, &attributes);
+    }
+}
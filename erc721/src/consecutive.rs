@@ -0,0 +1,85 @@
+use crate::{DataKey, DataKeyConsecutive, Error, MAX_CONSECUTIVE};
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
+use storage::Storage;
+
+/// Resolve the owner of a token.
+///
+/// Tokens minted with `mint_consecutive` have no `TokenOwner` entry until
+/// they are first transferred, their owner is read from the batch instead.
+pub fn owner_of(env: &Env, token_id: u32) -> Option<Address> {
+    if let Some(owner) = DataKey::TokenOwner(token_id).get(env) {
+        return Some(owner);
+    }
+    if DataKeyConsecutive::Burned(token_id).has(env) {
+        return None;
+    }
+    batch_owner(env, token_id)
+}
+
+/// Find the batch containing the token and return its initial owner
+fn batch_owner(env: &Env, token_id: u32) -> Option<Address> {
+    batch(env, token_id).map(|(owner, _)| owner)
+}
+
+/// Find the batch containing the token, return its initial owner and the
+/// ledger it was minted at
+pub fn batch(env: &Env, token_id: u32) -> Option<(Address, u32)> {
+    let starts: Vec<u32> = DataKeyConsecutive::Batches.get(env)?;
+    // Batches are allocated in increasing order, the vec is sorted
+    let start = match starts.binary_search(token_id) {
+        Ok(i) => starts.get_unchecked(i),
+        Err(0) => return None,
+        Err(i) => starts.get_unchecked(i - 1),
+    };
+    let (owner, last, ledger): (Address, u32, u32) = DataKeyConsecutive::Batch(start).get(env)?;
+    if token_id <= last {
+        Some((owner, ledger))
+    } else {
+        None
+    }
+}
+
+/// The lowest id above every token minted so far
+pub fn next_id(env: &Env) -> u32 {
+    DataKeyConsecutive::NextId.get(env).unwrap_or(0)
+}
+
+/// Make sure the next consecutive batch starts after the given token id
+pub fn reserve(env: &Env, token_id: u32) {
+    let next: u32 = DataKeyConsecutive::NextId.get(env).unwrap_or(0);
+    if token_id >= next {
+        let next = token_id
+            .checked_add(1)
+            .unwrap_or_else(|| panic_with_error!(env, Error::OutOfBounds));
+        DataKeyConsecutive::NextId.set(env, &next);
+    }
+}
+
+/// Record a burn, so the token is not resolved from its batch anymore
+#[cfg(feature = "burnable")]
+pub fn burn(env: &Env, token_id: u32) {
+    if batch_owner(env, token_id).is_some() {
+        DataKeyConsecutive::Burned(token_id).set(env, &true);
+    }
+}
+
+/// Record a new batch of `count` tokens owned by `to`, returns the first and last token ids
+pub fn mint(env: &Env, to: &Address, count: u32) -> (u32, u32) {
+    if count == 0 || count > MAX_CONSECUTIVE {
+        panic_with_error!(env, Error::OutOfBounds);
+    }
+    let first: u32 = DataKeyConsecutive::NextId.get(env).unwrap_or(0);
+    let last = first
+        .checked_add(count - 1)
+        .unwrap_or_else(|| panic_with_error!(env, Error::OutOfBounds));
+
+    let mut starts: Vec<u32> = DataKeyConsecutive::Batches
+        .get(env)
+        .unwrap_or_else(|| Vec::new(env));
+    starts.push_back(first);
+    DataKeyConsecutive::Batches.set(env, &starts);
+    DataKeyConsecutive::Batch(first).set(env, &(to.clone(), last, env.ledger().sequence()));
+    DataKeyConsecutive::NextId.set(env, &(last + 1));
+
+    (first, last)
+}
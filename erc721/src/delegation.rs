@@ -0,0 +1,38 @@
+//! Delegates, e.g. hot wallets, acting for the owners of the tokens through a
+//! delegation registry contract. Delegates can't transfer nor approve, the
+//! wrapping contract decides what they may do (update content, claim...).
+use crate::{consecutive, DataKeyDelegation, Error};
+use soroban_sdk::{contractclient, panic_with_error, Address, Env};
+use storage::Storage;
+
+#[contractclient(name = "DelegationRegistryClient")]
+pub trait DelegationRegistry {
+    fn check_delegate(
+        env: Env,
+        delegate: Address,
+        owner: Address,
+        contract: Address,
+        token_id: u32,
+    ) -> bool;
+}
+
+pub fn registry(env: &Env) -> Option<Address> {
+    DataKeyDelegation::Registry.get(env)
+}
+
+/// Whether `account` owns `token_id` or is one of its owner's delegates
+pub fn is_owner_or_delegate(env: &Env, account: &Address, token_id: u32) -> bool {
+    let owner = consecutive::owner_of(env, token_id)
+        .unwrap_or_else(|| panic_with_error!(env, Error::NotNFT));
+    if owner == *account {
+        return true;
+    }
+    registry(env).is_some_and(|registry| {
+        DelegationRegistryClient::new(env, &registry).check_delegate(
+            account,
+            &owner,
+            &env.current_contract_address(),
+            &token_id,
+        )
+    })
+}
@@ -0,0 +1,160 @@
+//! Enumerable indexes.
+//!
+//! Every entry is stored on its own persistent key, tokens are removed by
+//! moving the last entry of the list into the freed slot (swap and pop), so
+//! each update touches a constant number of entries.
+use crate::{DataKey, DataKeyEnumerable};
+use soroban_sdk::{Address, Env, Vec};
+use storage::Storage;
+
+/// Largest page returned by the paginated queries, each entry is one storage read
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+pub fn total_supply(env: &Env) -> u32 {
+    DataKeyEnumerable::TotalSupply.get(env).unwrap_or(0)
+}
+
+/// Append a token to the list of all tokens
+pub fn add_token(env: &Env, token_id: u32) {
+    let index = total_supply(env);
+    DataKeyEnumerable::IndexToken(index).set(env, &token_id);
+    DataKeyEnumerable::TokenIndex(token_id).set(env, &index);
+    DataKeyEnumerable::TotalSupply.set(env, &(index + 1));
+}
+
+/// Remove a token from the list of all tokens
+#[cfg(feature = "burnable")]
+pub fn remove_token(env: &Env, token_id: u32) {
+    let last = total_supply(env) - 1;
+    let index: u32 = DataKeyEnumerable::TokenIndex(token_id).get(env).unwrap();
+    if index != last {
+        let moved: u32 = DataKeyEnumerable::IndexToken(last).get(env).unwrap();
+        DataKeyEnumerable::IndexToken(index).set(env, &moved);
+        DataKeyEnumerable::TokenIndex(moved).set(env, &index);
+    }
+    DataKeyEnumerable::IndexToken(last).remove(env);
+    DataKeyEnumerable::TokenIndex(token_id).remove(env);
+    DataKeyEnumerable::TotalSupply.set(env, &last);
+}
+
+/// Append a token to the list of the owner and increment its balance
+pub fn add_owner_token(env: &Env, owner: &Address, token_id: u32) {
+    let index = balance(env, owner);
+    DataKeyEnumerable::OwnerIndexToken(owner.clone(), index).set(env, &token_id);
+    DataKeyEnumerable::OwnerTokenIndex(token_id).set(env, &index);
+    DataKey::Balance(owner.clone()).set(env, &(index + 1));
+}
+
+/// Append the tokens `first..=last` to the lists of all tokens and of the owner,
+/// the supply and the balance are written once
+pub fn add_tokens(env: &Env, owner: &Address, first: u32, last: u32) {
+    let supply = total_supply(env);
+    let balance = balance(env, owner);
+    for (offset, token_id) in (first..=last).enumerate() {
+        let offset = offset as u32;
+        DataKeyEnumerable::IndexToken(supply + offset).set(env, &token_id);
+        DataKeyEnumerable::TokenIndex(token_id).set(env, &(supply + offset));
+        DataKeyEnumerable::OwnerIndexToken(owner.clone(), balance + offset).set(env, &token_id);
+        DataKeyEnumerable::OwnerTokenIndex(token_id).set(env, &(balance + offset));
+    }
+    let count = last - first + 1;
+    DataKeyEnumerable::TotalSupply.set(env, &(supply + count));
+    DataKey::Balance(owner.clone()).set(env, &(balance + count));
+}
+
+/// Remove a token from the list of the owner and decrement its balance
+pub fn remove_owner_token(env: &Env, owner: &Address, token_id: u32) {
+    let last = balance(env, owner) - 1;
+    let index: u32 = DataKeyEnumerable::OwnerTokenIndex(token_id)
+        .get(env)
+        .unwrap();
+    if index != last {
+        let moved: u32 = DataKeyEnumerable::OwnerIndexToken(owner.clone(), last)
+            .get(env)
+            .unwrap();
+        DataKeyEnumerable::OwnerIndexToken(owner.clone(), index).set(env, &moved);
+        DataKeyEnumerable::OwnerTokenIndex(moved).set(env, &index);
+    }
+    DataKeyEnumerable::OwnerIndexToken(owner.clone(), last).remove(env);
+    DataKeyEnumerable::OwnerTokenIndex(token_id).remove(env);
+    DataKey::Balance(owner.clone()).set(env, &last);
+}
+
+/// Read up to `limit` tokens of the owner starting at index `start`
+pub fn owner_tokens(env: &Env, owner: &Address, start: u32, limit: u32) -> Vec<u32> {
+    let end = page_end(balance(env, owner), start, limit);
+    let mut page = Vec::new(env);
+    for index in start..end {
+        page.push_back(
+            DataKeyEnumerable::OwnerIndexToken(owner.clone(), index)
+                .get(env)
+                .unwrap(),
+        );
+    }
+    page
+}
+
+/// Read up to `limit` tokens of the collection starting at index `start`
+pub fn tokens(env: &Env, start: u32, limit: u32) -> Vec<u32> {
+    let end = page_end(total_supply(env), start, limit);
+    let mut page = Vec::new(env);
+    for index in start..end {
+        page.push_back(DataKeyEnumerable::IndexToken(index).get(env).unwrap());
+    }
+    page
+}
+
+/// End of a page, bounded by the length of the list and `MAX_PAGE_SIZE`
+fn page_end(len: u32, start: u32, limit: u32) -> u32 {
+    start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(len)
+}
+
+fn balance(env: &Env, owner: &Address) -> u32 {
+    DataKey::Balance(owner.clone()).get(env).unwrap_or(0)
+}
+
+/// Walk every index and check they agree with each other, with the owners
+/// and with the balances.
+///
+/// The cost is linear in the total supply, it is meant for tests and
+/// simulations only.
+#[cfg(any(test, feature = "testutils"))]
+pub fn check_invariants(env: &Env) -> bool {
+    use soroban_sdk::Map;
+
+    let supply = total_supply(env);
+    let mut owned: Map<Address, u32> = Map::new(env);
+    for index in 0..supply {
+        let Some(token_id) = DataKeyEnumerable::IndexToken(index).get::<u32>(env) else {
+            return false;
+        };
+        if DataKeyEnumerable::TokenIndex(token_id).get::<u32>(env) != Some(index) {
+            return false;
+        }
+        let Some(owner) = crate::consecutive::owner_of(env, token_id) else {
+            return false;
+        };
+        let Some(owner_index) = DataKeyEnumerable::OwnerTokenIndex(token_id).get::<u32>(env) else {
+            return false;
+        };
+        if owner_index >= balance(env, &owner)
+            || DataKeyEnumerable::OwnerIndexToken(owner.clone(), owner_index).get::<u32>(env)
+                != Some(token_id)
+        {
+            return false;
+        }
+        owned.set(owner.clone(), owned.get(owner).unwrap_or(0) + 1);
+    }
+    // Nothing left past the end of the lists
+    if DataKeyEnumerable::IndexToken(supply).has(env) {
+        return false;
+    }
+    for (owner, count) in owned.iter() {
+        if balance(env, &owner) != count
+            || DataKeyEnumerable::OwnerIndexToken(owner, count).has(env)
+        {
+            return false;
+        }
+    }
+    true
+}
@@ -0,0 +1,11 @@
+use crate::AttributeValue;
+use soroban_sdk::{Address, Env, Map, Symbol, Vec};
+pub trait ERC721Attributes {
+    fn metadata_role(env: Env) -> Address;
+    fn attribute(env: Env, token_id: u32, key: Symbol) -> Option<AttributeValue>;
+    fn attributes(env: Env, token_id: u32) -> Map<Symbol, AttributeValue>;
+    fn attributes_of(env: Env, token_ids: Vec<u32>) -> Vec<Map<Symbol, AttributeValue>>;
+    fn collection_attributes(env: Env) -> Map<Symbol, AttributeValue>;
+    fn set_attribute(env: Env, token_id: u32, key: Symbol, value: Option<AttributeValue>);
+    fn set_collection_attribute(env: Env, key: Symbol, value: Option<AttributeValue>);
+}
@@ -1,6 +1,9 @@
-use soroban_sdk::{Address, Env};
+use soroban_sdk::{Address, Env, Vec};
 pub trait ERC721Enumerable {
     fn total_supply(env: Env) -> u32;
     fn token_by_index(env: Env, index: u32) -> u32;
     fn token_of_owner_by_index(env: Env, owner: Address, index: u32) -> u32;
+    fn tokens_of_owner(env: Env, owner: Address, start: u32, limit: u32) -> Vec<u32>;
+    fn all_tokens(env: Env, start: u32, limit: u32) -> Vec<u32>;
+    fn owners_of(env: Env, token_ids: Vec<u32>) -> Vec<Option<Address>>;
 }
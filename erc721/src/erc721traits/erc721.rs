@@ -19,4 +19,8 @@ pub trait ERC721 {
     );
     fn get_approved(env: Env, token_id: u32) -> Option<Address>;
     fn is_approval_for_all(env: Env, owner: Address, operator: Address) -> bool;
+    fn owner_of(env: Env, token_id: u32) -> Address;
+    fn approval_expiration(env: Env, token_id: u32) -> Option<u32>;
+    fn operator_expiration(env: Env, owner: Address, operator: Address) -> Option<u32>;
+    fn revoke_all_operators(env: Env, owner: Address);
 }
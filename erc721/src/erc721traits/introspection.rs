@@ -0,0 +1,4 @@
+use soroban_sdk::{Env, Symbol};
+pub trait ERC721Introspection {
+    fn supports_interface(env: Env, interface_id: Symbol) -> bool;
+}
@@ -0,0 +1,6 @@
+use soroban_sdk::{Address, Env};
+pub trait ERC721Lockable {
+    fn locked(env: Env, token_id: u32) -> bool;
+    fn lock(env: Env, owner: Address, locker: Address, token_id: u32);
+    fn unlock(env: Env, caller: Address, token_id: u32);
+}
@@ -3,4 +3,6 @@ pub trait ERC721Metadata {
     fn name(env: Env) -> String;
     fn symbol(env: Env) -> String;
     fn token_uri(env: Env, token_id: u32) -> String;
+    fn contract_uri(env: Env) -> String;
+    fn is_metadata_frozen(env: Env, token_id: u32) -> bool;
 }
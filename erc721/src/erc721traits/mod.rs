@@ -1,4 +1,13 @@
+pub mod attributes;
 pub mod burnable;
 pub mod enumerable;
 pub mod erc721;
+pub mod introspection;
+pub mod lockable;
 pub mod metadata;
+pub mod nested;
+pub mod operator_filter;
+pub mod regulated;
+pub mod rentable;
+pub mod sep50;
+pub mod votes;
@@ -0,0 +1,15 @@
+use crate::ParentToken;
+use soroban_sdk::{Address, Env, Vec};
+pub trait ERC721Nested {
+    fn root_owner_of(env: Env, token_id: u32) -> Address;
+    fn parent_of(env: Env, token_id: u32) -> Option<ParentToken>;
+    fn children_of(env: Env, contract: Address, token_id: u32) -> Vec<u32>;
+    fn transfer_to_parent(
+        env: Env,
+        spender: Address,
+        from: Address,
+        token_id: u32,
+        parent: ParentToken,
+    );
+    fn transfer_from_parent(env: Env, caller: Address, token_id: u32, to: Address);
+}
@@ -0,0 +1,6 @@
+use crate::OperatorFilter;
+use soroban_sdk::{Address, Env};
+pub trait ERC721OperatorFilter {
+    fn operator_filter(env: Env) -> OperatorFilter;
+    fn is_operator_allowed(env: Env, operator: Address) -> bool;
+}
@@ -0,0 +1,10 @@
+use soroban_sdk::{Address, Env};
+pub trait ERC721Regulated {
+    fn regulator(env: Env) -> Option<Address>;
+    fn clawback_enabled(env: Env) -> bool;
+    fn is_token_frozen(env: Env, token_id: u32) -> bool;
+    fn is_account_frozen(env: Env, account: Address) -> bool;
+    fn freeze_token(env: Env, token_id: u32, frozen: bool);
+    fn freeze_account(env: Env, account: Address, frozen: bool);
+    fn clawback(env: Env, token_id: u32, to: Address);
+}
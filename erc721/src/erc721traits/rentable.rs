@@ -0,0 +1,12 @@
+use soroban_sdk::{Address, Env};
+pub trait ERC721Rentable {
+    fn set_user(
+        env: Env,
+        caller: Address,
+        token_id: u32,
+        user: Option<Address>,
+        expires_ledger: u32,
+    );
+    fn user_of(env: Env, token_id: u32) -> Option<Address>;
+    fn user_expires(env: Env, token_id: u32) -> u32;
+}
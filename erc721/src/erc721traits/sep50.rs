@@ -0,0 +1,32 @@
+use soroban_sdk::{Address, Env, String};
+
+/// SEP-0050 non-fungible token interface
+pub trait NonFungibleToken {
+    fn balance(env: Env, account: Address) -> u32;
+    fn owner_of(env: Env, token_id: u32) -> Address;
+    fn transfer(env: Env, from: Address, to: Address, token_id: u32);
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, token_id: u32);
+    fn approve(
+        env: Env,
+        approver: Address,
+        approved: Address,
+        token_id: u32,
+        live_until_ledger: u32,
+    );
+    fn approve_for_all(env: Env, owner: Address, operator: Address, live_until_ledger: u32);
+    fn get_approved(env: Env, token_id: u32) -> Option<Address>;
+    fn is_approved_for_all(env: Env, owner: Address, operator: Address) -> bool;
+}
+
+/// SEP-0050 metadata, part of the core interface when the `metadata` feature is enabled
+pub trait NonFungibleMetadata {
+    fn name(env: Env) -> String;
+    fn symbol(env: Env) -> String;
+    fn token_uri(env: Env, token_id: u32) -> String;
+}
+
+/// SEP-0050 burnable extension
+pub trait NonFungibleBurnable {
+    fn burn(env: Env, from: Address, token_id: u32);
+    fn burn_from(env: Env, spender: Address, from: Address, token_id: u32);
+}
@@ -0,0 +1,8 @@
+use soroban_sdk::{Address, Env};
+pub trait ERC721Votes {
+    fn get_votes(env: Env, account: Address) -> u32;
+    fn get_past_votes(env: Env, account: Address, ledger: u32) -> u32;
+    fn get_past_total_supply(env: Env, ledger: u32) -> u32;
+    fn delegates(env: Env, account: Address) -> Option<Address>;
+    fn delegate(env: Env, account: Address, delegatee: Address);
+}
@@ -0,0 +1,356 @@
+#![cfg(all(test, not(feature = "soulbound")))]
+//! Stateful fuzzing: random sequences of valid operations are applied both to
+//! the contract and to a plain model, the two are compared after each step.
+//!
+//! Contract failures can't be caught from the tests, so only operations the
+//! model knows to be allowed are generated, none with soulbound tokens.
+//!
+//! `ERC721_FUZZ_SEEDS` and `ERC721_FUZZ_STEPS` extend the run, a failing
+//! sequence is replayed with `ERC721_FUZZ_SEED`.
+extern crate std;
+
+use super::*;
+use soroban_sdk::testutils::Address as _;
+use std::collections::{BTreeMap, BTreeSet};
+use std::vec::Vec as StdVec;
+
+const ADDRESSES: usize = 6;
+const DEFAULT_SEEDS: u64 = 4;
+const DEFAULT_STEPS: u32 = 25;
+
+/// xorshift64*, good enough to pick operations and reproducible everywhere
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // The state must never be zero
+        Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len())]
+    }
+}
+
+#[derive(Debug)]
+enum Op {
+    Mint {
+        to: usize,
+        token_id: u32,
+    },
+    MintConsecutive {
+        to: usize,
+        count: u32,
+    },
+    Transfer {
+        spender: usize,
+        to: usize,
+        token_id: u32,
+    },
+    Approve {
+        caller: usize,
+        operator: Option<usize>,
+        token_id: u32,
+    },
+    SetApprovalForAll {
+        owner: usize,
+        operator: usize,
+        approved: bool,
+    },
+    #[cfg(feature = "burnable")]
+    Burn {
+        caller: usize,
+        token_id: u32,
+    },
+}
+
+/// What the contract state should be, addresses are indexes in the address list
+#[derive(Default)]
+struct Model {
+    owners: BTreeMap<u32, usize>,
+    approved: BTreeMap<u32, usize>,
+    operators: BTreeSet<(usize, usize)>,
+    // Highest token id ever minted + 1, consecutive batches start there
+    next_id: u32,
+}
+
+impl Model {
+    fn tokens_of(&self, owner: usize) -> StdVec<u32> {
+        self.owners
+            .iter()
+            .filter(|(_, o)| **o == owner)
+            .map(|(token_id, _)| *token_id)
+            .collect()
+    }
+
+    /// Addresses allowed to act on the token, as spender or approver
+    fn approvers(&self, token_id: u32) -> StdVec<usize> {
+        let owner = self.owners[&token_id];
+        let mut approvers = std::vec![owner];
+        approvers.extend(
+            self.operators
+                .iter()
+                .filter(|(o, _)| *o == owner)
+                .map(|(_, operator)| *operator),
+        );
+        approvers
+    }
+
+    fn random_op(&self, rng: &mut Rng) -> Op {
+        let tokens: StdVec<u32> = self.owners.keys().copied().collect();
+        let kinds = if cfg!(feature = "burnable") { 6 } else { 5 };
+        let kind = if tokens.is_empty() {
+            rng.below(2)
+        } else {
+            rng.below(kinds)
+        };
+        match kind {
+            0 => {
+                // Fresh ids, gaps in batches and burned ids can all be minted
+                let token_id = loop {
+                    let token_id = rng.below(self.next_id as usize + 8) as u32;
+                    if !self.owners.contains_key(&token_id) {
+                        break token_id;
+                    }
+                };
+                Op::Mint {
+                    to: rng.below(ADDRESSES),
+                    token_id,
+                }
+            }
+            1 => Op::MintConsecutive {
+                to: rng.below(ADDRESSES),
+                count: 1 + rng.below(MAX_CONSECUTIVE.min(5) as usize) as u32,
+            },
+            2 => {
+                let token_id = rng.pick(&tokens);
+                let mut spenders = self.approvers(token_id);
+                spenders.extend(self.approved.get(&token_id));
+                Op::Transfer {
+                    spender: rng.pick(&spenders),
+                    to: rng.below(ADDRESSES),
+                    token_id,
+                }
+            }
+            3 => {
+                let token_id = rng.pick(&tokens);
+                let operator = match rng.below(4) {
+                    0 => None,
+                    _ => Some(rng.below(ADDRESSES)),
+                };
+                Op::Approve {
+                    caller: rng.pick(&self.approvers(token_id)),
+                    operator,
+                    token_id,
+                }
+            }
+            4 => Op::SetApprovalForAll {
+                owner: rng.below(ADDRESSES),
+                operator: rng.below(ADDRESSES),
+                approved: rng.below(3) != 0,
+            },
+            #[cfg(feature = "burnable")]
+            _ => {
+                let token_id = rng.pick(&tokens);
+                Op::Burn {
+                    caller: rng.pick(&self.approvers(token_id)),
+                    token_id,
+                }
+            }
+            #[cfg(not(feature = "burnable"))]
+            _ => unreachable!(),
+        }
+    }
+
+    fn apply(&mut self, op: &Op) {
+        match *op {
+            Op::Mint { to, token_id } => {
+                self.owners.insert(token_id, to);
+                self.next_id = self.next_id.max(token_id + 1);
+            }
+            Op::MintConsecutive { to, count } => {
+                for token_id in self.next_id..self.next_id + count {
+                    self.owners.insert(token_id, to);
+                }
+                self.next_id += count;
+            }
+            Op::Transfer { to, token_id, .. } => {
+                self.owners.insert(token_id, to);
+                self.approved.remove(&token_id);
+            }
+            Op::Approve {
+                operator, token_id, ..
+            } => match operator {
+                Some(operator) => {
+                    self.approved.insert(token_id, operator);
+                }
+                None => {
+                    self.approved.remove(&token_id);
+                }
+            },
+            Op::SetApprovalForAll {
+                owner,
+                operator,
+                approved,
+            } => {
+                if approved {
+                    self.operators.insert((owner, operator));
+                } else {
+                    self.operators.remove(&(owner, operator));
+                }
+            }
+            #[cfg(feature = "burnable")]
+            Op::Burn { token_id, .. } => {
+                self.owners.remove(&token_id);
+                self.approved.remove(&token_id);
+            }
+        }
+    }
+}
+
+fn execute(client: &ERC721ContractClient, addresses: &[Address], model: &Model, op: &Op) {
+    match *op {
+        Op::Mint { to, token_id } => client.mint(&addresses[to], &token_id),
+        Op::MintConsecutive { to, count } => {
+            let first = client.mint_consecutive(&addresses[to], &count);
+            assert_eq!(first, model.next_id, "unexpected first id of the batch");
+        }
+        Op::Transfer {
+            spender,
+            to,
+            token_id,
+        } => {
+            let from = &addresses[model.owners[&token_id]];
+            client.transfer_from(&addresses[spender], from, &addresses[to], &token_id)
+        }
+        Op::Approve {
+            caller,
+            operator,
+            token_id,
+        } => client.approve(
+            &addresses[caller],
+            &operator.map(|operator| addresses[operator].clone()),
+            &token_id,
+            &NON_EXPIRING,
+        ),
+        Op::SetApprovalForAll {
+            owner,
+            operator,
+            approved,
+        } => client.set_approval_for_all(
+            &addresses[owner],
+            &addresses[owner],
+            &addresses[operator],
+            &approved,
+            &NON_EXPIRING,
+        ),
+        #[cfg(feature = "burnable")]
+        Op::Burn { caller, token_id } => client.burn(&addresses[caller], &token_id),
+    }
+}
+
+fn check(env: &Env, client: &ERC721ContractClient, addresses: &[Address], model: &Model) {
+    for (index, address) in addresses.iter().enumerate() {
+        let tokens = model.tokens_of(index);
+        assert_eq!(
+            client.balance_of(address),
+            tokens.len() as u32,
+            "balance of {index}"
+        );
+
+        #[cfg(feature = "enumerable")]
+        {
+            let mut owned: StdVec<u32> = client
+                .tokens_of_owner(address, &0, &enumerable::MAX_PAGE_SIZE)
+                .iter()
+                .collect();
+            owned.sort();
+            assert_eq!(owned, tokens, "tokens of {index}");
+        }
+
+        for (operator, other) in addresses.iter().enumerate() {
+            assert_eq!(
+                client.is_approval_for_all(address, other),
+                model.operators.contains(&(index, operator)),
+                "operator {operator} of {index}"
+            );
+        }
+    }
+    for (token_id, owner) in model.owners.iter() {
+        assert_eq!(
+            client.owner_of(token_id),
+            addresses[*owner],
+            "owner of {token_id}"
+        );
+        assert_eq!(
+            client.get_approved(token_id),
+            model.approved.get(token_id).map(|a| addresses[*a].clone()),
+            "approved of {token_id}"
+        );
+    }
+
+    #[cfg(feature = "enumerable")]
+    {
+        assert_eq!(client.total_supply(), model.owners.len() as u32);
+        env.as_contract(&client.address, || {
+            assert!(check_enumerable_invariants(env), "enumerable indexes");
+        });
+    }
+    #[cfg(not(feature = "enumerable"))]
+    let _ = env;
+}
+
+fn run(seed: u64, steps: u32) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+    let contract_id = env.register_contract(None, ERC721Contract);
+    let client = ERC721ContractClient::new(&env, &contract_id);
+    client.initialize(
+        &Address::generate(&env),
+        #[cfg(feature = "metadata")]
+        &String::from_str(&env, "Fuzz"),
+        #[cfg(feature = "metadata")]
+        &String::from_str(&env, "FUZZ"),
+    );
+
+    let addresses: StdVec<Address> = (0..ADDRESSES).map(|_| Address::generate(&env)).collect();
+    let mut rng = Rng::new(seed);
+    let mut model = Model::default();
+    for step in 0..steps {
+        let op = model.random_op(&mut rng);
+        // Only printed when the test fails
+        std::println!("seed {seed} step {step}: {op:?}");
+        execute(&client, &addresses, &model, &op);
+        model.apply(&op);
+        check(&env, &client, &addresses, &model);
+    }
+}
+
+fn env_var<T: core::str::FromStr>(name: &str) -> Option<T> {
+    std::env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+}
+
+#[test]
+fn fuzz_invariants() {
+    let steps = env_var("ERC721_FUZZ_STEPS").unwrap_or(DEFAULT_STEPS);
+    if let Some(seed) = env_var("ERC721_FUZZ_SEED") {
+        return run(seed, steps);
+    }
+    for seed in 0..env_var("ERC721_FUZZ_SEEDS").unwrap_or(DEFAULT_SEEDS) {
+        run(seed, steps);
+    }
+}
@@ -0,0 +1,87 @@
+//! Interface names answered by `supports_interface`, the ones implemented by
+//! the crate follow the enabled features and the wrapping contract can
+//! register its own (`royalty`...). `consecutive` is left to the wrappers
+//! exposing `mint_consecutive`.
+use crate::DataKeyIntrospection;
+use soroban_sdk::{Env, Symbol, Vec};
+use storage::Storage;
+
+pub const INTROSPECTION: &str = "introspection";
+pub const ERC721: &str = "erc721";
+pub const LOCKABLE: &str = "lockable";
+pub const CONSECUTIVE: &str = "consecutive";
+pub const ENUMERABLE: &str = "enumerable";
+pub const METADATA: &str = "metadata";
+pub const ONCHAIN_METADATA: &str = "onchain_metadata";
+pub const BURNABLE: &str = "burnable";
+pub const SOULBOUND: &str = "soulbound";
+pub const RENTABLE: &str = "rentable";
+pub const VOTES: &str = "votes";
+pub const SEP50: &str = "sep50";
+pub const NESTED: &str = "nested";
+pub const OPERATOR_FILTER: &str = "operator_filter";
+pub const REGULATED: &str = "regulated";
+pub const ATTRIBUTES: &str = "attributes";
+pub const VOUCHERS: &str = "vouchers";
+pub const DELEGATION: &str = "delegation";
+pub const PROVENANCE: &str = "provenance";
+pub const ROYALTY: &str = "royalty";
+
+/// Interfaces implemented by the crate with the enabled features
+const BUILTIN: &[&str] = &[
+    INTROSPECTION,
+    ERC721,
+    #[cfg(feature = "lockable")]
+    LOCKABLE,
+    #[cfg(feature = "enumerable")]
+    ENUMERABLE,
+    #[cfg(feature = "metadata")]
+    METADATA,
+    #[cfg(feature = "onchain-metadata")]
+    ONCHAIN_METADATA,
+    #[cfg(feature = "burnable")]
+    BURNABLE,
+    #[cfg(feature = "soulbound")]
+    SOULBOUND,
+    #[cfg(feature = "rentable")]
+    RENTABLE,
+    #[cfg(feature = "votes")]
+    VOTES,
+    #[cfg(feature = "sep50")]
+    SEP50,
+    #[cfg(feature = "nested")]
+    NESTED,
+    #[cfg(feature = "operator-filter")]
+    OPERATOR_FILTER,
+    #[cfg(feature = "regulated")]
+    REGULATED,
+    #[cfg(feature = "attributes")]
+    ATTRIBUTES,
+    #[cfg(feature = "vouchers")]
+    VOUCHERS,
+    #[cfg(feature = "delegation")]
+    DELEGATION,
+    #[cfg(feature = "provenance")]
+    PROVENANCE,
+];
+
+pub(crate) fn supports(env: &Env, interface_id: &Symbol) -> bool {
+    BUILTIN
+        .iter()
+        .any(|name| Symbol::new(env, name) == *interface_id)
+        || registered(env).contains(interface_id)
+}
+
+pub(crate) fn register(env: &Env, interface_id: Symbol) {
+    let mut interfaces = registered(env);
+    if !interfaces.contains(&interface_id) {
+        interfaces.push_back(interface_id);
+        DataKeyIntrospection::Interfaces.set(env, &interfaces);
+    }
+}
+
+fn registered(env: &Env) -> Vec<Symbol> {
+    DataKeyIntrospection::Interfaces
+        .get(env)
+        .unwrap_or_else(|| Vec::new(env))
+}
@@ -0,0 +1,220 @@
+//! Minimal no_std JSON writer and base64 encoder to serve token metadata
+//! as a `data:` uri.
+//!
+//! Everything is written into fixed size buffers, so the work done by a call
+//! is bounded by `MAX_JSON_LEN` and stays within the Soroban budget.
+use crate::Error;
+use soroban_sdk::{panic_with_error, Env, String};
+
+/// Largest JSON document that can be built
+pub const MAX_JSON_LEN: usize = 2048;
+
+const DATA_URI_PREFIX: &[u8] = b"data:application/json;base64,";
+
+/// Largest data uri produced from a JSON document
+pub const MAX_DATA_URI_LEN: usize = DATA_URI_PREFIX.len() + MAX_JSON_LEN.div_ceil(3) * 4;
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub struct JsonBuilder {
+    buf: [u8; MAX_JSON_LEN],
+    len: usize,
+    comma: bool,
+    overflow: bool,
+}
+
+impl Default for JsonBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonBuilder {
+    pub fn new() -> Self {
+        JsonBuilder {
+            buf: [0; MAX_JSON_LEN],
+            len: 0,
+            comma: false,
+            overflow: false,
+        }
+    }
+
+    pub fn begin_object(&mut self) -> &mut Self {
+        self.separator();
+        self.push(b"{");
+        self.comma = false;
+        self
+    }
+
+    pub fn end_object(&mut self) -> &mut Self {
+        self.push(b"}");
+        self.comma = true;
+        self
+    }
+
+    pub fn begin_array(&mut self) -> &mut Self {
+        self.separator();
+        self.push(b"[");
+        self.comma = false;
+        self
+    }
+
+    pub fn end_array(&mut self) -> &mut Self {
+        self.push(b"]");
+        self.comma = true;
+        self
+    }
+
+    /// Write an object key, the value must follow
+    pub fn key(&mut self, key: &str) -> &mut Self {
+        self.separator();
+        self.quoted(key.as_bytes());
+        self.push(b":");
+        self.comma = false;
+        self
+    }
+
+    pub fn str(&mut self, value: &str) -> &mut Self {
+        self.separator();
+        self.quoted(value.as_bytes());
+        self.comma = true;
+        self
+    }
+
+    pub fn string(&mut self, value: &String) -> &mut Self {
+        let len = value.len() as usize;
+        if len > MAX_JSON_LEN {
+            self.overflow = true;
+            return self;
+        }
+        let mut tmp = [0u8; MAX_JSON_LEN];
+        value.copy_into_slice(&mut tmp[..len]);
+        self.separator();
+        self.quoted(&tmp[..len]);
+        self.comma = true;
+        self
+    }
+
+    pub fn u32(&mut self, value: u32) -> &mut Self {
+        self.u128(false, value as u128)
+    }
+
+    pub fn i128(&mut self, value: i128) -> &mut Self {
+        self.u128(value < 0, value.unsigned_abs())
+    }
+
+    /// The JSON written so far
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    pub fn to_string(&self, env: &Env) -> String {
+        self.check(env);
+        String::from_bytes(env, self.as_bytes())
+    }
+
+    /// Encode the document as a `data:application/json;base64,` uri
+    pub fn to_data_uri(&self, env: &Env) -> String {
+        self.check(env);
+        let mut out = [0u8; MAX_DATA_URI_LEN];
+        out[..DATA_URI_PREFIX.len()].copy_from_slice(DATA_URI_PREFIX);
+        let len = base64_encode(self.as_bytes(), &mut out[DATA_URI_PREFIX.len()..]);
+        String::from_bytes(env, &out[..DATA_URI_PREFIX.len() + len])
+    }
+
+    fn check(&self, env: &Env) {
+        if self.overflow {
+            panic_with_error!(env, Error::MetadataTooLarge);
+        }
+    }
+
+    fn u128(&mut self, negative: bool, mut n: u128) -> &mut Self {
+        let mut digits = [0u8; 40];
+        let mut i = digits.len();
+        loop {
+            i -= 1;
+            digits[i] = b'0' + (n % 10) as u8;
+            n /= 10;
+            if n == 0 {
+                break;
+            }
+        }
+        if negative {
+            i -= 1;
+            digits[i] = b'-';
+        }
+        self.separator();
+        self.push(&digits[i..]);
+        self.comma = true;
+        self
+    }
+
+    fn separator(&mut self) {
+        if self.comma {
+            self.push(b",");
+        }
+    }
+
+    fn quoted(&mut self, value: &[u8]) {
+        self.push(b"\"");
+        for &c in value {
+            match c {
+                b'"' => self.push(b"\\\""),
+                b'\\' => self.push(b"\\\\"),
+                b'\n' => self.push(b"\\n"),
+                b'\r' => self.push(b"\\r"),
+                b'\t' => self.push(b"\\t"),
+                0..=0x1f => {
+                    const HEX: &[u8; 16] = b"0123456789abcdef";
+                    self.push(&[
+                        b'\\',
+                        b'u',
+                        b'0',
+                        b'0',
+                        HEX[(c >> 4) as usize],
+                        HEX[(c & 0xf) as usize],
+                    ]);
+                }
+                _ => self.push(&[c]),
+            }
+        }
+        self.push(b"\"");
+    }
+
+    fn push(&mut self, bytes: &[u8]) {
+        let end = self.len + bytes.len();
+        if end > MAX_JSON_LEN {
+            self.overflow = true;
+            return;
+        }
+        self.buf[self.len..end].copy_from_slice(bytes);
+        self.len = end;
+    }
+}
+
+/// Standard base64 with padding, returns the number of bytes written
+pub fn base64_encode(input: &[u8], out: &mut [u8]) -> usize {
+    let mut len = 0;
+    for chunk in input.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+        out[len] = BASE64[(n >> 18) as usize & 0x3f];
+        out[len + 1] = BASE64[(n >> 12) as usize & 0x3f];
+        out[len + 2] = if chunk.len() > 1 {
+            BASE64[(n >> 6) as usize & 0x3f]
+        } else {
+            b'='
+        };
+        out[len + 3] = if chunk.len() > 2 {
+            BASE64[n as usize & 0x3f]
+        } else {
+            b'='
+        };
+        len += 4;
+    }
+    len
+}
@@ -1,117 +1,133 @@
 #![no_std]
 
+pub use crate::approval::NON_EXPIRING;
+pub use crate::erc721traits::attributes::ERC721Attributes;
 pub use crate::erc721traits::burnable::ERC721Burnable;
 pub use crate::erc721traits::enumerable::ERC721Enumerable;
 pub use crate::erc721traits::erc721::ERC721;
+pub use crate::erc721traits::introspection::ERC721Introspection;
+pub use crate::erc721traits::lockable::ERC721Lockable;
 pub use crate::erc721traits::metadata::ERC721Metadata;
+pub use crate::erc721traits::nested::ERC721Nested;
+pub use crate::erc721traits::operator_filter::ERC721OperatorFilter;
+pub use crate::erc721traits::regulated::ERC721Regulated;
+pub use crate::erc721traits::rentable::ERC721Rentable;
+pub use crate::erc721traits::sep50::{NonFungibleBurnable, NonFungibleMetadata, NonFungibleToken};
+pub use crate::erc721traits::votes::ERC721Votes;
+#[cfg(feature = "sep50")]
+pub use crate::sep50::SEP50Contract;
 pub use crate::types::*;
 use storage::Storage;
 
-use soroban_sdk::{panic_with_error, Address, BytesN, Env, IntoVal, Map, String, Val, Vec};
+#[cfg(feature = "attributes")]
+use soroban_sdk::Map;
+#[cfg(feature = "metadata")]
+use soroban_sdk::String;
+use soroban_sdk::{panic_with_error, Address, BytesN, Env, IntoVal, Symbol, Val, Vec};
 
+mod approval;
+#[cfg(feature = "attributes")]
+mod attributes;
+mod consecutive;
+#[cfg(feature = "delegation")]
+pub mod delegation;
+#[cfg(feature = "enumerable")]
+mod enumerable;
 mod erc721traits;
+pub mod introspection;
+#[cfg(feature = "onchain-metadata")]
+pub mod json;
+#[cfg(feature = "metadata")]
+mod metadata;
+#[cfg(feature = "nested")]
+pub mod nested;
+#[cfg(feature = "operator-filter")]
+pub mod operator_filter;
+#[cfg(feature = "provenance")]
+mod provenance;
+#[cfg(feature = "regulated")]
+mod regulated;
+#[cfg(feature = "sep50")]
+mod sep50;
 mod types;
+#[cfg(feature = "votes")]
+mod votes;
+#[cfg(feature = "vouchers")]
+mod voucher;
 
+#[cfg(test)]
+mod fuzz;
 #[cfg(test)]
 mod tests;
 
+/// Ledger entries one transaction may write, the network's
+/// `tx_max_write_ledger_entries` setting (25 since launch). `Env::default()`
+/// doesn't enforce it, the batch entry points are sized from it instead.
+pub const MAX_WRITE_ENTRIES: u32 = 25;
+
+/// Entries written once by a `mint_consecutive` call whatever its size: the
+/// batch range, the owner's balance and the contract instance, plus the
+/// checkpoint and its count of the delegate and of the total supply with `votes`.
+#[cfg(feature = "enumerable")]
+const CONSECUTIVE_WRITES: u32 = 3 + if cfg!(feature = "votes") { 4 } else { 0 };
+
+/// Largest `mint_consecutive` batch. The batch is a single range entry only
+/// without `enumerable`, the indexes write 4 more entries per token, so with
+/// them a batch fits what is left of `MAX_WRITE_ENTRIES`.
+#[cfg(feature = "enumerable")]
+pub const MAX_CONSECUTIVE: u32 = (MAX_WRITE_ENTRIES - CONSECUTIVE_WRITES) / 4;
+#[cfg(not(feature = "enumerable"))]
+pub const MAX_CONSECUTIVE: u32 = 5000;
+
+/// Entries written by minting a token to a new owner: its `TokenOwner` and the
+/// owner's `Balance`, plus the 4 enumerable indexes, the provenance log and the
+/// checkpoint and its count of the owner's delegate with those features.
+pub const MINT_WRITES: u32 = 2
+    + if cfg!(feature = "enumerable") { 4 } else { 0 }
+    + if cfg!(feature = "provenance") { 1 } else { 0 }
+    + if cfg!(feature = "votes") { 2 } else { 0 };
+
+/// Entries written once by a call minting tokens: the contract instance, plus
+/// the checkpoint and its count of the total supply with `votes`.
+const MINT_CALL_WRITES: u32 = 1 + if cfg!(feature = "votes") { 2 } else { 0 };
+
+/// Entries minted by one `airdrop` call, as many as `MAX_WRITE_ENTRIES` allows
+/// when every recipient is new: (25 - 1) / 6 = 4 with the default features.
+pub const MAX_AIRDROP_SIZE: u32 = (MAX_WRITE_ENTRIES - MINT_CALL_WRITES) / MINT_WRITES;
+
 #[cfg_attr(test, soroban_sdk::contract)]
 pub struct ERC721Contract;
 
 #[cfg_attr(test, soroban_sdk::contractimpl)]
 impl ERC721 for ERC721Contract {
     fn balance_of(env: Env, owner: Address) -> u32 {
-        DataKey::Balance(owner)
-            .extend(&env, 1000)
-            .get(&env)
-            .unwrap_or(0)
+        let key = DataKey::Balance(owner);
+        // Extending a missing entry would fail
+        if !key.has(&env) {
+            return 0;
+        }
+        key.extend(&env, 1000).get(&env).unwrap_or(0)
     }
 
     fn transfer_from(env: Env, spender: Address, from: Address, to: Address, token_id: u32) {
-        spender.require_auth();
-        let is_sender_approved = if spender != from {
-            let has_approved =
-                if let Some(approved) = DataKey::Approved(token_id).get::<Address>(&env) {
-                    // Clear the approval on transfer
-                    DataKey::Approved(token_id).remove(&env);
-                    approved == spender
-                } else {
-                    false
-                };
-            if !has_approved {
-                DataKey::Operator(from.clone(), spender).has(&env)
-            } else {
-                true
-            }
-        } else {
-            true
-        };
-        if !is_sender_approved {
-            panic_with_error!(&env, Error::NotAuthorized);
-        }
-
-        if let Some(addr) = DataKey::TokenOwner(token_id).get::<Address>(&env) {
-            if addr == from {
-                if cfg!(feature = "enumerable") && from != to {
-                    // update enumerable datai
-                    let from_index_key = DataKeyEnumerable::OwnerIndexToken(from.clone());
-                    let from_token_key = DataKeyEnumerable::OwnerTokenIndex(from.clone());
-                    let to_index_key = DataKeyEnumerable::OwnerIndexToken(to.clone());
-                    let to_token_key = DataKeyEnumerable::OwnerTokenIndex(to.clone());
-                    let mut from_index: Vec<u32> =
-                        from_index_key.get(&env).unwrap_or_else(|| Vec::new(&env));
-                    let mut from_token: Map<u32, u32> =
-                        from_token_key.get(&env).unwrap_or_else(|| Map::new(&env));
-                    let mut to_index: Vec<u32> =
-                        to_index_key.get(&env).unwrap_or_else(|| Vec::new(&env));
-                    let mut to_token: Map<u32, u32> =
-                        to_token_key.get(&env).unwrap_or_else(|| Map::new(&env));
-
-                    // Remove token from index of from address
-                    from_index.remove(from_token.get(token_id).unwrap());
-                    from_token.remove(token_id);
-
-                    // Remove token from index of to address
-                    to_token.set(token_id, to_index.len());
-                    to_index.push_back(token_id);
-
-                    // Update from address vec and map
-                    from_index_key.set(&env, &from_index);
-                    from_token_key.set(&env, &from_token);
-                    DataKey::Balance(from.clone()).set(&env, &from_index.len());
-
-                    // Update to address vec and map
-                    to_token_key.set(&env, &to_token);
-                    to_index_key.set(&env, &to_index);
-                    DataKey::Balance(to.clone()).set(&env, &to_index.len());
-                }
-                DataKey::TokenOwner(token_id).set(&env, &to);
-            } else {
-                panic_with_error!(&env, Error::NotOwner);
-            }
-        } else {
-            panic_with_error!(&env, Error::NotNFT);
-        }
+        transfer(&env, &spender, &from, &to, token_id);
+        Event::Transfer.publish(&env, (from, to, token_id));
     }
-    fn approve(env: Env, caller: Address, operator: Option<Address>, token_id: u32, ttl: u32) {
-        if let Some(owner) = DataKey::TokenOwner(token_id).get::<Address>(&env) {
-            if owner == caller {
-                owner.require_auth();
-            } else if DataKey::Operator(owner, caller.clone())
-                .get::<bool>(&env)
-                .unwrap_or(false)
-            {
-                caller.require_auth();
-            }
-        } else {
-            panic_with_error!(&env, Error::NotNFT);
-        }
-        if let Some(to_approve) = operator {
-            DataKey::Approved(token_id).set(&env, &to_approve);
-            DataKey::Approved(token_id).extend(&env, ttl);
-        } else {
-            DataKey::Approved(token_id).remove(&env);
-        }
+    fn approve(
+        env: Env,
+        caller: Address,
+        operator: Option<Address>,
+        token_id: u32,
+        expiration_ledger: u32,
+    ) {
+        approve(
+            &env,
+            &caller,
+            operator.as_ref(),
+            token_id,
+            expiration_ledger,
+        );
+        Event::Approve.publish(&env, (token_id, operator, expiration_ledger));
     }
     fn set_approval_for_all(
         env: Env,
@@ -119,33 +135,38 @@ impl ERC721 for ERC721Contract {
         owner: Address,
         operator: Address,
         approved: bool,
-        ttl: u32,
+        expiration_ledger: u32,
     ) {
-        if owner == caller {
-            owner.require_auth();
-        } else if DataKey::Operator(owner.clone(), caller.clone())
-            .get::<bool>(&env)
-            .unwrap_or(false)
-        {
-            caller.require_auth();
-        } else {
-            panic_with_error!(&env, Error::NotAuthorized);
-        }
-        let key = DataKey::Operator(owner, operator);
-        if approved {
-            key.set(&env, &true);
-            key.extend(&env, ttl);
-        } else {
-            key.remove(&env);
-        }
+        approve_for_all(
+            &env,
+            &caller,
+            &owner,
+            &operator,
+            approved,
+            expiration_ledger,
+        );
+        Event::ApproveForAll.publish(&env, (owner, operator, approved, expiration_ledger));
     }
     fn get_approved(env: Env, token_id: u32) -> Option<Address> {
-        DataKey::Approved(token_id).get(&env).unwrap_or(None)
+        approval::approved(&env, token_id)
     }
     fn is_approval_for_all(env: Env, owner: Address, operator: Address) -> bool {
-        DataKey::Operator(owner, operator)
-            .get(&env)
-            .unwrap_or(false)
+        approval::is_operator(&env, &owner, &operator)
+    }
+    fn owner_of(env: Env, token_id: u32) -> Address {
+        consecutive::owner_of(&env, token_id)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotNFT))
+    }
+    fn approval_expiration(env: Env, token_id: u32) -> Option<u32> {
+        approval::approval(&env, token_id).map(|approval| approval.expiration_ledger)
+    }
+    fn operator_expiration(env: Env, owner: Address, operator: Address) -> Option<u32> {
+        approval::operator_grant(&env, &owner, &operator).map(|grant| grant.expiration_ledger)
+    }
+    fn revoke_all_operators(env: Env, owner: Address) {
+        owner.require_auth();
+        approval::revoke_all(&env, &owner);
+        Event::RevokeAllOperators.publish(&env, owner);
     }
 }
 
@@ -159,9 +180,96 @@ impl ERC721Metadata for ERC721Contract {
         DatakeyMetadata::Symbol.get(&env).unwrap()
     }
     fn token_uri(env: Env, token_id: u32) -> String {
-        DatakeyMetadata::Uri(token_id)
+        metadata::token_uri(&env, token_id).unwrap_or_else(|| String::from_str(&env, "no uri"))
+    }
+    fn contract_uri(env: Env) -> String {
+        DatakeyMetadata::ContractUri
             .get(&env)
-            .unwrap_or_else(|| String::from_str(&env, "no uri"))
+            .unwrap_or_else(|| String::from_str(&env, ""))
+    }
+    fn is_metadata_frozen(env: Env, token_id: u32) -> bool {
+        metadata::is_frozen(&env, token_id)
+    }
+}
+
+#[cfg(feature = "metadata")]
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC721Contract {
+    /// Set the uri prefix of every token without a uri of its own, the token id
+    /// is appended in decimal.
+    pub fn set_base_uri(env: Env, uri: String) {
+        // Authorization should be handled by the caller of the actual implementation
+        metadata::check_all(&env);
+        metadata::check_base_uri(&env, &uri);
+        DatakeyMetadata::BaseUri.set(&env, &uri);
+        Event::BatchMetadataUpdate.publish(&env, (0u32, u32::MAX));
+    }
+
+    /// Set the uri of a single token, it takes precedence over the base uri
+    pub fn set_token_uri(env: Env, token_id: u32, uri: String) {
+        // Authorization should be handled by the caller of the actual implementation
+        metadata::check_token(&env, token_id);
+        DatakeyMetadata::Uri(token_id).set(&env, &uri);
+        let v: Val = token_id.into();
+        Event::MetadataUpdate.publish(&env, v);
+    }
+
+    /// Set the collection level metadata uri
+    pub fn set_contract_uri(env: Env, uri: String) {
+        // Authorization should be handled by the caller of the actual implementation
+        metadata::check_all(&env);
+        DatakeyMetadata::ContractUri.set(&env, &uri);
+        Event::ContractURIUpdated.publish(&env, uri);
+    }
+
+    /// Make the uri and attributes of `token_id` permanent. A token relying on
+    /// the base uri keeps the uri it has now.
+    pub fn freeze_metadata(env: Env, token_id: u32) {
+        // Authorization should be handled by the caller of the actual implementation
+
+        if consecutive::owner_of(&env, token_id).is_none() {
+            panic_with_error!(&env, Error::NotNFT);
+        }
+        metadata::check_token(&env, token_id);
+        let has_own_uri = DatakeyMetadata::Uri(token_id).has(&env)
+            || DatakeyMetadata::Onchain(token_id).has(&env);
+        let uri = metadata::token_uri(&env, token_id);
+        if let (false, Some(uri)) = (has_own_uri, &uri) {
+            DatakeyMetadata::Uri(token_id).set(&env, uri);
+        }
+        DatakeyMetadata::Frozen(token_id).set(&env, &true);
+        let uri = uri.unwrap_or_else(|| String::from_str(&env, ""));
+        Event::PermanentURI.publish(&env, (uri, Some(token_id)));
+    }
+
+    /// Make the metadata of the whole collection permanent, including the
+    /// tokens minted later
+    pub fn freeze_all_metadata(env: Env) {
+        // Authorization should be handled by the caller of the actual implementation
+
+        metadata::check_all(&env);
+        DatakeyMetadata::FrozenAll.set(&env, &true);
+        let base_uri = DatakeyMetadata::BaseUri
+            .get(&env)
+            .unwrap_or_else(|| String::from_str(&env, ""));
+        Event::PermanentURI.publish(&env, (base_uri, Option::<u32>::None));
+    }
+}
+
+#[cfg(feature = "onchain-metadata")]
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC721Contract {
+    /// Store the metadata of a token on-chain, `token_uri` then serves it as a
+    /// base64 encoded JSON data uri unless the token has a uri of its own
+    pub fn set_token_metadata(env: Env, token_id: u32, metadata: TokenMetadata) {
+        // Authorization should be handled by the caller of the actual implementation
+
+        metadata::check_token(&env, token_id);
+        // Fail early rather than when the uri is read
+        metadata::onchain_uri(&env, token_id, &metadata);
+        DatakeyMetadata::Onchain(token_id).set(&env, &metadata);
+        let v: Val = token_id.into();
+        Event::MetadataUpdate.publish(&env, v);
     }
 }
 
@@ -169,108 +277,548 @@ impl ERC721Metadata for ERC721Contract {
 #[cfg_attr(test, soroban_sdk::contractimpl)]
 impl ERC721Enumerable for ERC721Contract {
     fn total_supply(env: Env) -> u32 {
-        DataKeyEnumerable::IndexToken
-            .get::<Vec<u32>>(&env)
-            .unwrap()
-            .len()
+        enumerable::total_supply(&env)
     }
     fn token_by_index(env: Env, index: u32) -> u32 {
-        DataKeyEnumerable::IndexToken
-            .get::<Vec<u32>>(&env)
-            .unwrap()
-            .get(index)
+        DataKeyEnumerable::IndexToken(index)
+            .get(&env)
             .unwrap_or_else(|| panic_with_error!(&env, Error::OutOfBounds))
     }
     fn token_of_owner_by_index(env: Env, owner: Address, index: u32) -> u32 {
-        DataKeyEnumerable::OwnerIndexToken(owner)
-            .get::<Vec<u32>>(&env)
-            .unwrap_or_else(|| panic_with_error!(&env, Error::OutOfBounds))
-            .get(index)
+        DataKeyEnumerable::OwnerIndexToken(owner, index)
+            .get(&env)
             .unwrap_or_else(|| panic_with_error!(&env, Error::OutOfBounds))
     }
+    fn tokens_of_owner(env: Env, owner: Address, start: u32, limit: u32) -> Vec<u32> {
+        enumerable::owner_tokens(&env, &owner, start, limit)
+    }
+    fn all_tokens(env: Env, start: u32, limit: u32) -> Vec<u32> {
+        enumerable::tokens(&env, start, limit)
+    }
+    fn owners_of(env: Env, token_ids: Vec<u32>) -> Vec<Option<Address>> {
+        if token_ids.len() > enumerable::MAX_PAGE_SIZE {
+            panic_with_error!(&env, Error::OutOfBounds);
+        }
+        let mut owners = Vec::new(&env);
+        for token_id in token_ids.iter() {
+            owners.push_back(consecutive::owner_of(&env, token_id));
+        }
+        owners
+    }
 }
 
 #[cfg(feature = "burnable")]
 #[cfg_attr(test, soroban_sdk::contractimpl)]
 impl ERC721Burnable for ERC721Contract {
     fn burn(env: Env, caller: Address, token_id: u32) {
-        let owner: Address = DataKey::TokenOwner(token_id)
+        let owner: Address = consecutive::owner_of(&env, token_id)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotNFT));
+        if owner != caller && !approval::is_operator(&env, &owner, &caller) {
+            panic_with_error!(&env, Error::NotAuthorized);
+        }
+        caller.require_auth();
+
+        burn(&env, &owner, token_id);
+        let v: Val = token_id.into();
+        Event::Burn.publish(&env, v);
+    }
+}
+
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC721Introspection for ERC721Contract {
+    fn supports_interface(env: Env, interface_id: Symbol) -> bool {
+        introspection::supports(&env, &interface_id)
+    }
+}
+
+#[cfg(feature = "lockable")]
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC721Lockable for ERC721Contract {
+    fn locked(env: Env, token_id: u32) -> bool {
+        if consecutive::owner_of(&env, token_id).is_none() {
+            panic_with_error!(&env, Error::NotNFT);
+        }
+        cfg!(feature = "soulbound") || DataKeyLock::Locker(token_id).has(&env)
+    }
+    fn lock(env: Env, owner: Address, locker: Address, token_id: u32) {
+        let current: Address = consecutive::owner_of(&env, token_id)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotNFT));
+        if owner != current {
+            panic_with_error!(&env, Error::NotOwner);
+        }
+        owner.require_auth();
+
+        // The locker, e.g. a staking or escrow contract, is the only one able
+        // to release the token
+        let key = DataKeyLock::Locker(token_id);
+        if key.has(&env) {
+            panic_with_error!(&env, Error::TokenLocked);
+        }
+        key.set(&env, &locker);
+
+        let v: Val = token_id.into();
+        Event::Locked.publish(&env, v);
+    }
+    fn unlock(env: Env, caller: Address, token_id: u32) {
+        let key = DataKeyLock::Locker(token_id);
+        let locker: Address = key
             .get(&env)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotLocked));
+        if locker != caller {
+            panic_with_error!(&env, Error::NotAuthorized);
+        }
+        caller.require_auth();
+        key.remove(&env);
+
+        let v: Val = token_id.into();
+        Event::Unlocked.publish(&env, v);
+    }
+}
+
+#[cfg(feature = "rentable")]
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC721Rentable for ERC721Contract {
+    fn set_user(
+        env: Env,
+        caller: Address,
+        token_id: u32,
+        user: Option<Address>,
+        expires_ledger: u32,
+    ) {
+        let owner: Address = consecutive::owner_of(&env, token_id)
             .unwrap_or_else(|| panic_with_error!(&env, Error::NotNFT));
-        if owner == caller {
-            owner.require_auth();
-        } else if DataKey::Operator(owner.clone(), caller.clone())
-            .get::<bool>(&env)
-            .unwrap_or(false)
-        {
-            caller.require_auth();
+        if !is_approved_or_owner(&env, &owner, &caller, token_id) {
+            panic_with_error!(&env, Error::NotAuthorized);
+        }
+        caller.require_auth();
+
+        let key = DataKeyRentable::User(token_id);
+        if let Some(user) = user {
+            key.set(
+                &env,
+                &UserInfo {
+                    user: user.clone(),
+                    expires_ledger,
+                },
+            );
+            Event::UpdateUser.publish(&env, (token_id, Some(user), expires_ledger));
         } else {
+            clear_user(&env, token_id);
+        }
+    }
+    fn user_of(env: Env, token_id: u32) -> Option<Address> {
+        DataKeyRentable::User(token_id)
+            .get::<UserInfo>(&env)
+            .filter(|info| info.expires_ledger >= env.ledger().sequence())
+            .map(|info| info.user)
+    }
+    fn user_expires(env: Env, token_id: u32) -> u32 {
+        DataKeyRentable::User(token_id)
+            .get::<UserInfo>(&env)
+            .map(|info| info.expires_ledger)
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(feature = "votes")]
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC721Votes for ERC721Contract {
+    fn get_votes(env: Env, account: Address) -> u32 {
+        votes::latest(&env, &votes::History::Account(account))
+    }
+    fn get_past_votes(env: Env, account: Address, ledger: u32) -> u32 {
+        votes::past(&env, &votes::History::Account(account), ledger)
+    }
+    fn get_past_total_supply(env: Env, ledger: u32) -> u32 {
+        votes::past(&env, &votes::History::Total, ledger)
+    }
+    fn delegates(env: Env, account: Address) -> Option<Address> {
+        votes::delegates(&env, &account)
+    }
+    fn delegate(env: Env, account: Address, delegatee: Address) {
+        account.require_auth();
+
+        let previous = votes::delegates(&env, &account);
+        DataKeyVotes::Delegate(account.clone()).set(&env, &delegatee);
+        Event::DelegateChanged
+            .publish(&env, (account.clone(), previous.clone(), delegatee.clone()));
+
+        // Voting units are the tokens held by the account
+        let units = Self::balance_of(env.clone(), account);
+        votes::move_delegate_votes(&env, previous, Some(delegatee), units);
+    }
+}
+
+#[cfg(feature = "nested")]
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC721Nested for ERC721Contract {
+    fn root_owner_of(env: Env, token_id: u32) -> Address {
+        nested::root_owner(&env, token_id)
+    }
+    fn parent_of(env: Env, token_id: u32) -> Option<ParentToken> {
+        nested::parent(&env, token_id)
+    }
+    fn children_of(env: Env, contract: Address, token_id: u32) -> Vec<u32> {
+        nested::children(&env, &contract, token_id)
+    }
+    /// Give `token_id` to the `parent` token, the contract holds it from now on
+    fn transfer_to_parent(
+        env: Env,
+        spender: Address,
+        from: Address,
+        token_id: u32,
+        parent: ParentToken,
+    ) {
+        nested::check_parent(&env, token_id, &parent);
+        let this = env.current_contract_address();
+        transfer(&env, &spender, &from, &this, token_id);
+        nested::attach(&env, token_id, &parent);
+
+        Event::Transfer.publish(&env, (from.clone(), this, token_id));
+        Event::ReceivedChild.publish(&env, (from, token_id, parent.contract, parent.token_id));
+    }
+    /// Take `token_id` out of its parent, only the root owner or its operators can
+    fn transfer_from_parent(env: Env, caller: Address, token_id: u32, to: Address) {
+        let parent = nested::parent(&env, token_id)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotNested));
+        let root_owner = nested::root_owner(&env, token_id);
+        if root_owner != caller && !approval::is_operator(&env, &root_owner, &caller) {
             panic_with_error!(&env, Error::NotAuthorized);
         }
+        caller.require_auth();
+        #[cfg(feature = "operator-filter")]
+        if caller != root_owner {
+            operator_filter::check(&env, &caller);
+        }
+        #[cfg(feature = "regulated")]
+        {
+            regulated::check_token(&env, token_id);
+            regulated::check_accounts(&env, &[&caller, &root_owner, &to]);
+        }
 
-        DataKey::Approved(token_id).remove(&env);
-        DataKey::TokenOwner(token_id).remove(&env);
+        let this = env.current_contract_address();
+        nested::detach(&env, token_id, &parent);
+        move_token(&env, &this, &to, token_id);
 
-        if cfg!(feature = "enumerable") {
-            let mut owned_index: Vec<u32> = DataKeyEnumerable::IndexToken.get(&env).unwrap();
-            let mut owned_token_index: Map<u32, u32> =
-                DataKeyEnumerable::TokenIndex.get(&env).unwrap();
-            let from_index_key = DataKeyEnumerable::OwnerIndexToken(owner.clone());
-            let from_token_key = DataKeyEnumerable::OwnerTokenIndex(owner.clone());
+        Event::Transfer.publish(&env, (this, to.clone(), token_id));
+        Event::TransferChild.publish(&env, (parent.contract, parent.token_id, to, token_id));
+    }
+}
 
-            let mut from_index: Vec<u32> =
-                from_index_key.get(&env).unwrap_or_else(|| Vec::new(&env));
-            let mut from_token: Map<u32, u32> =
-                from_token_key.get(&env).unwrap_or_else(|| Map::new(&env));
+#[cfg(feature = "operator-filter")]
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC721OperatorFilter for ERC721Contract {
+    fn operator_filter(env: Env) -> OperatorFilter {
+        operator_filter::filter(&env)
+    }
+    fn is_operator_allowed(env: Env, operator: Address) -> bool {
+        operator_filter::is_allowed(&env, &operator)
+    }
+}
 
-            from_index.remove(from_token.get(token_id).unwrap());
-            from_token.remove(token_id);
-            owned_index.remove(owned_token_index.get(token_id).unwrap());
-            owned_token_index.remove(token_id);
+#[cfg(feature = "operator-filter")]
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC721Contract {
+    /// Choose how operators are filtered, the allow and deny lists are kept
+    /// when switching
+    pub fn set_operator_filter(env: Env, filter: OperatorFilter) {
+        // Authorization should be handled by the caller of the actual implementation
 
-            from_index_key.set(&env, &from_index);
-            from_token_key.set(&env, &from_token);
-            DataKeyEnumerable::IndexToken.set(&env, &owned_index);
-            DataKeyEnumerable::TokenIndex.set(&env, &owned_token_index);
+        DataKeyOperatorFilter::Filter.set(&env, &filter);
+        Event::OperatorFilterUpdated.publish(&env, filter);
+    }
+
+    /// Add `operator` to the allowlist, or remove it
+    pub fn allow_operator(env: Env, operator: Address, allowed: bool) {
+        // Authorization should be handled by the caller of the actual implementation
 
-            DataKey::Balance(owner).set(&env, &from_index.len());
+        let key = DataKeyOperatorFilter::Allowed(operator.clone());
+        if allowed {
+            key.set(&env, &true);
         } else {
-            let balance_key = DataKey::Balance(owner.clone());
-            let balance = balance_key.get(&env).unwrap_or(0);
-            balance_key.set(&env, &(balance - 1));
+            key.remove(&env);
         }
-        let v: Val = token_id.into();
-        Event::Burn.publish(&env, v);
+        Event::OperatorAllowed.publish(&env, (operator, allowed));
+    }
+
+    /// Add `operator` to the denylist, or remove it
+    pub fn deny_operator(env: Env, operator: Address, denied: bool) {
+        // Authorization should be handled by the caller of the actual implementation
+
+        let key = DataKeyOperatorFilter::Denied(operator.clone());
+        if denied {
+            key.set(&env, &true);
+        } else {
+            key.remove(&env);
+        }
+        Event::OperatorDenied.publish(&env, (operator, denied));
     }
 }
 
+#[cfg(feature = "regulated")]
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC721Regulated for ERC721Contract {
+    fn regulator(env: Env) -> Option<Address> {
+        regulated::regulator(&env)
+    }
+    /// Whether the regulator can take tokens back, i.e. one is set
+    fn clawback_enabled(env: Env) -> bool {
+        regulated::regulator(&env).is_some()
+    }
+    fn is_token_frozen(env: Env, token_id: u32) -> bool {
+        regulated::is_token_frozen(&env, token_id)
+    }
+    fn is_account_frozen(env: Env, account: Address) -> bool {
+        regulated::is_account_frozen(&env, &account)
+    }
+    fn freeze_token(env: Env, token_id: u32, frozen: bool) {
+        regulated::require_regulator(&env);
+        if consecutive::owner_of(&env, token_id).is_none() {
+            panic_with_error!(&env, Error::NotNFT);
+        }
+
+        let key = DataKeyRegulated::FrozenToken(token_id);
+        if frozen {
+            key.set(&env, &true);
+        } else {
+            key.remove(&env);
+        }
+        Event::TokenFrozen.publish(&env, (token_id, frozen));
+    }
+    fn freeze_account(env: Env, account: Address, frozen: bool) {
+        regulated::require_regulator(&env);
+
+        let key = DataKeyRegulated::FrozenAccount(account.clone());
+        if frozen {
+            key.set(&env, &true);
+        } else {
+            key.remove(&env);
+        }
+        Event::AccountFrozen.publish(&env, (account, frozen));
+    }
+    /// Give `token_id` to `to` without the owner authorization
+    fn clawback(env: Env, token_id: u32, to: Address) {
+        regulated::require_regulator(&env);
+        let owner = consecutive::owner_of(&env, token_id)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotNFT));
+
+        #[cfg(feature = "nested")]
+        if let Some(parent) = nested::parent(&env, token_id) {
+            nested::detach(&env, token_id, &parent);
+        }
+        // The locker can't hold the token back either
+        #[cfg(feature = "lockable")]
+        {
+            let lock = DataKeyLock::Locker(token_id);
+            if lock.has(&env) {
+                lock.remove(&env);
+                let v: Val = token_id.into();
+                Event::Unlocked.publish(&env, v);
+            }
+        }
+        move_token(&env, &owner, &to, token_id);
+
+        Event::Transfer.publish(&env, (owner.clone(), to.clone(), token_id));
+        Event::Clawback.publish(&env, (owner, to, token_id));
+    }
+}
+
+#[cfg(feature = "regulated")]
 #[cfg_attr(test, soroban_sdk::contractimpl)]
 impl ERC721Contract {
-    pub fn initialize(
-        env: Env,
-        admin: Address,
-        #[cfg(feature = "metadata")] name: String,
-        #[cfg(feature = "metadata")] symbol: String,
-    ) {
-        if Admin::User.has(&env) {
-            panic!("Already initialized")
+    /// Hand the regulator role to another address, or remove it with `None`:
+    /// clawback is disabled and the tokens and accounts frozen stay frozen
+    pub fn set_regulator(env: Env, regulator: Option<Address>) {
+        get_admin(&env).require_auth();
+
+        match &regulator {
+            Some(regulator) => DataKeyRegulated::Regulator.set(&env, regulator),
+            None => DataKeyRegulated::Regulator.remove(&env),
+        }
+        Event::RegulatorChanged.publish(&env, regulator);
+    }
+}
+
+#[cfg(feature = "attributes")]
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC721Attributes for ERC721Contract {
+    fn metadata_role(env: Env) -> Address {
+        attributes::metadata_role(&env)
+    }
+    fn attribute(env: Env, token_id: u32, key: Symbol) -> Option<AttributeValue> {
+        attributes::get(&env, &DataKeyAttributes::Token(token_id)).get(key)
+    }
+    fn attributes(env: Env, token_id: u32) -> Map<Symbol, AttributeValue> {
+        attributes::get(&env, &DataKeyAttributes::Token(token_id))
+    }
+    fn attributes_of(env: Env, token_ids: Vec<u32>) -> Vec<Map<Symbol, AttributeValue>> {
+        if token_ids.len() > attributes::MAX_BATCH_SIZE {
+            panic_with_error!(&env, Error::OutOfBounds);
         }
-        Admin::User.set(&env, &admin);
+        let mut all = Vec::new(&env);
+        for token_id in token_ids.iter() {
+            all.push_back(attributes::get(&env, &DataKeyAttributes::Token(token_id)));
+        }
+        all
+    }
+    fn collection_attributes(env: Env) -> Map<Symbol, AttributeValue> {
+        attributes::get(&env, &DataKeyAttributes::Collection)
+    }
+    /// Set an attribute of `token_id`, or remove it with `None`
+    fn set_attribute(env: Env, token_id: u32, key: Symbol, value: Option<AttributeValue>) {
+        attributes::metadata_role(&env).require_auth();
+        if consecutive::owner_of(&env, token_id).is_none() {
+            panic_with_error!(&env, Error::NotNFT);
+        }
+        metadata::check_token(&env, token_id);
+
+        let storage_key = DataKeyAttributes::Token(token_id);
+        attributes::set(&env, &storage_key, key.clone(), value.clone());
+        // The on-chain metadata must still fit with the new attribute
+        #[cfg(feature = "onchain-metadata")]
+        if let Some(metadata) = DatakeyMetadata::Onchain(token_id).get(&env) {
+            metadata::onchain_uri(&env, token_id, &metadata);
+        }
+        Event::AttributeUpdated.publish(&env, (Some(token_id), key, value));
+    }
+    /// Set an attribute of the collection, or remove it with `None`
+    fn set_collection_attribute(env: Env, key: Symbol, value: Option<AttributeValue>) {
+        attributes::metadata_role(&env).require_auth();
+        metadata::check_all(&env);
+
+        let storage_key = DataKeyAttributes::Collection;
+        attributes::set(&env, &storage_key, key.clone(), value.clone());
+        Event::AttributeUpdated.publish(&env, (Option::<u32>::None, key, value));
+    }
+}
 
-        env.storage().instance().extend_ttl(10000, 10000);
-        if cfg!(feature = "metadata") {
-            env.storage().instance().set(&DatakeyMetadata::Name, &name);
-            env.storage()
-                .instance()
-                .set(&DatakeyMetadata::Symbol, &symbol);
+#[cfg(feature = "attributes")]
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC721Contract {
+    /// Give the metadata role to another address, back to the admin with `None`
+    pub fn set_metadata_role(env: Env, role: Option<Address>) {
+        get_admin(&env).require_auth();
+
+        match &role {
+            Some(role) => DataKeyAttributes::MetadataRole.set(&env, role),
+            None => DataKeyAttributes::MetadataRole.remove(&env),
         }
+        Event::MetadataRoleChanged.publish(&env, role);
+    }
+}
+
+#[cfg(feature = "vouchers")]
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC721Contract {
+    /// Set the ed25519 public key signing the mint vouchers
+    pub fn set_voucher_signer(env: Env, public_key: BytesN<32>) {
+        // Authorization should be handled by the caller of the actual implementation
+
+        DataKeyVoucher::Signer.set(&env, &public_key);
+        Event::VoucherSignerChanged.publish(&env, public_key);
+    }
 
-        if cfg!(feature = "enumerable") {
-            DataKeyEnumerable::IndexToken.set(&env, &Vec::<u32>::new(&env));
-            DataKeyEnumerable::TokenIndex.set(&env, &Map::<u32, u32>::new(&env));
+    pub fn voucher_signer(env: Env) -> Option<BytesN<32>> {
+        voucher::signer(&env)
+    }
+
+    /// Mint the token of a signed voucher to `redeemer`, who pays its price to
+    /// the admin, and return the token id. Each voucher is redeemed once.
+    pub fn redeem_voucher(
+        env: Env,
+        redeemer: Address,
+        voucher: MintVoucher,
+        signature: BytesN<64>,
+    ) -> u32 {
+        redeemer.require_auth();
+        let digest = voucher::consume(&env, &redeemer, &voucher, &signature);
+
+        let token_id = voucher
+            .token_id
+            .unwrap_or_else(|| consecutive::next_id(&env));
+        if consecutive::owner_of(&env, token_id).is_some() {
+            panic_with_error!(&env, Error::AlreadyMinted);
+        }
+        if voucher.price > 0 {
+            soroban_sdk::token::Client::new(&env, &voucher.asset).transfer(
+                &redeemer,
+                &get_admin(&env),
+                &voucher.price,
+            );
+        }
+        if voucher.uri.len() > 0 {
+            Self::set_token_uri(env.clone(), token_id, voucher.uri);
         }
+        Self::mint(env.clone(), redeemer.clone(), token_id);
+        Event::VoucherRedeemed.publish(&env, (digest, redeemer, token_id));
+        token_id
     }
+}
+
+#[cfg(feature = "delegation")]
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC721Contract {
+    /// Set the delegation registry asked by `is_owner_or_delegate`
+    pub fn set_delegation_registry(env: Env, registry: Address) {
+        // Authorization should be handled by the caller of the actual implementation
+
+        DataKeyDelegation::Registry.set(&env, &registry);
+        Event::DelegationRegistryChanged.publish(&env, registry);
+    }
+
+    pub fn delegation_registry(env: Env) -> Option<Address> {
+        delegation::registry(&env)
+    }
+
+    pub fn is_owner_or_delegate(env: Env, account: Address, token_id: u32) -> bool {
+        delegation::is_owner_or_delegate(&env, &account, token_id)
+    }
+}
+
+#[cfg(feature = "provenance")]
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC721Contract {
+    /// Read up to `limit` of the latest ownership changes of `token_id`, oldest first
+    pub fn provenance(env: Env, token_id: u32, start: u32, limit: u32) -> Vec<ProvenanceEntry> {
+        provenance::page(&env, token_id, start, limit)
+    }
+
+    pub fn provenance_length(env: Env) -> u32 {
+        provenance::length(&env)
+    }
+
+    /// Set how many ownership changes are kept per token, up to `MAX_LENGTH`
+    pub fn set_provenance_length(env: Env, length: u32) {
+        // Authorization should be handled by the caller of the actual implementation
+
+        provenance::set_length(&env, length);
+        Event::ProvenanceLengthChanged.publish(&env, length);
+    }
+}
+
+#[cfg(feature = "metadata")]
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC721Contract {
+    pub fn initialize(env: Env, admin: Address, name: String, symbol: String) {
+        init(&env, &admin);
+        env.storage().instance().set(&DatakeyMetadata::Name, &name);
+        env.storage()
+            .instance()
+            .set(&DatakeyMetadata::Symbol, &symbol);
+    }
+}
 
+// The contract macros don't support feature gated arguments, hence the two versions
+#[cfg(not(feature = "metadata"))]
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC721Contract {
+    pub fn initialize(env: Env, admin: Address) {
+        init(&env, &admin);
+    }
+}
+
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl ERC721Contract {
     pub fn upgrade(env: Env, hash: BytesN<32>) {
         get_admin(&env).require_auth();
         env.deployer().update_current_contract_wasm(hash);
@@ -280,47 +828,339 @@ impl ERC721Contract {
         // Authorization should be handled by the caller of the actual implementation
         // get_admin(&env).require_auth();
 
-        if !env.storage().instance().has(&DataKey::TokenOwner(token_id)) {
-            DataKey::TokenOwner(token_id).set(&env, &to);
-
-            if cfg!(feature = "enumerable") {
-                let mut owned_index: Vec<u32> = DataKeyEnumerable::IndexToken.get(&env).unwrap();
-                let mut owned_token_index: Map<u32, u32> =
-                    DataKeyEnumerable::TokenIndex.get(&env).unwrap();
-
-                let mut owner_index: Vec<u32> = DataKeyEnumerable::OwnerIndexToken(to.clone())
-                    .get(&env)
-                    .unwrap_or_else(|| Vec::new(&env));
-                let mut owner_token_index: Map<u32, u32> =
-                    DataKeyEnumerable::OwnerTokenIndex(to.clone())
-                        .get(&env)
-                        .unwrap_or_else(|| Map::new(&env));
-
-                owned_token_index.set(token_id, owned_index.len());
-                owned_index.push_back(token_id);
-
-                owner_token_index.set(token_id, owner_index.len());
-                owner_index.push_back(token_id);
-
-                DataKeyEnumerable::IndexToken.set(&env, &owned_index);
-                DataKeyEnumerable::TokenIndex.set(&env, &owned_token_index);
-                DataKeyEnumerable::OwnerIndexToken(to.clone()).set(&env, &owner_index);
-                DataKeyEnumerable::OwnerTokenIndex(to.clone()).set(&env, &owner_token_index);
-
-                DataKey::Balance(to.clone()).set(&env, &owner_index.len());
-            } else {
-                let key = DataKey::Balance(to.clone());
-                let balance: u32 = key.get(&env).unwrap_or(0);
-                key.set(&env, &(balance + 1));
+        mint(&env, &to, token_id);
+        publish_mint(&env, &to, token_id);
+    }
+
+    /// Mint each `(recipient, token_id)` entry, skipping the ids already owned by
+    /// their recipient, so a batch cut short or sent twice can be submitted again.
+    /// Ids owned by someone else are left untouched and counted as conflicts.
+    ///
+    /// At most `MAX_AIRDROP_SIZE` entries are handled per call to stay within the
+    /// ledger entries a transaction may write, the rest is resumed from `summary.next`.
+    pub fn airdrop(env: Env, recipients: Vec<(Address, u32)>) -> AirdropSummary {
+        // Authorization should be handled by the caller of the actual implementation
+
+        let mut summary = AirdropSummary {
+            minted: 0,
+            skipped: 0,
+            conflicts: 0,
+            next: recipients.len().min(MAX_AIRDROP_SIZE),
+        };
+        for (to, token_id) in recipients.iter().take(summary.next as usize) {
+            match consecutive::owner_of(&env, token_id) {
+                Some(owner) if owner == to => summary.skipped += 1,
+                Some(_) => summary.conflicts += 1,
+                None => {
+                    mint(&env, &to, token_id);
+                    publish_mint(&env, &to, token_id);
+                    summary.minted += 1;
+                }
             }
         }
-        let mut v: Vec<Val> = Vec::new(&env);
-        v.push_back(to.into_val(&env));
-        v.push_back(token_id.into());
-        Event::Mint.publish(&env, v);
+        Event::Airdrop.publish(&env, summary.clone());
+        summary
+    }
+
+    /// Answer `true` to `supports_interface(interface_id)` from now on, for
+    /// the interfaces implemented by the wrapping contract itself
+    pub fn register_interface(env: Env, interface_id: Symbol) {
+        // Authorization should be handled by the caller of the actual implementation
+
+        introspection::register(&env, interface_id);
+    }
+
+    /// Mint `count` tokens with consecutive ids to `to` and return the first token id.
+    ///
+    /// A single batch entry is recorded and the owner of each token is resolved
+    /// from it until the token is first transferred. One `ConsecutiveMint` event
+    /// is published for the whole batch (ERC2309). `count` is capped by
+    /// `MAX_CONSECUTIVE`.
+    pub fn mint_consecutive(env: Env, to: Address, count: u32) -> u32 {
+        // Authorization should be handled by the caller of the actual implementation
+
+        let (first, last) = consecutive::mint(&env, &to, count);
+        #[cfg(feature = "enumerable")]
+        enumerable::add_tokens(&env, &to, first, last);
+        #[cfg(not(feature = "enumerable"))]
+        add_balance(&env, &to, count);
+        #[cfg(feature = "votes")]
+        votes::move_voting_units(&env, None, Some(&to), count);
+        Event::ConsecutiveMint.publish(&env, (to, first, last));
+        first
+    }
+}
+
+/// Move a token on behalf of `spender`, which must be the owner, approved or an operator
+fn transfer(env: &Env, spender: &Address, from: &Address, to: &Address, token_id: u32) {
+    spender.require_auth();
+    let is_sender_approved = spender == from
+        || approval::approved(env, token_id).as_ref() == Some(spender)
+        || approval::is_operator(env, from, spender);
+    if !is_sender_approved {
+        panic_with_error!(env, Error::NotAuthorized);
+    }
+    #[cfg(feature = "operator-filter")]
+    if spender != from {
+        operator_filter::check(env, spender);
+    }
+    #[cfg(feature = "regulated")]
+    {
+        regulated::check_token(env, token_id);
+        regulated::check_accounts(env, &[spender, from, to]);
+    }
+    if cfg!(feature = "soulbound") {
+        panic_with_error!(env, Error::NonTransferable);
+    }
+    #[cfg(feature = "lockable")]
+    if DataKeyLock::Locker(token_id).has(env) {
+        panic_with_error!(env, Error::TokenLocked);
+    }
+
+    // Nested tokens only leave through `transfer_from_parent`
+    if cfg!(feature = "nested") && DataKeyNested::Parent(token_id).has(env) {
+        panic_with_error!(env, Error::TokenNested);
+    }
+
+    match consecutive::owner_of(env, token_id) {
+        Some(owner) if owner == *from => {}
+        Some(_) => panic_with_error!(env, Error::NotOwner),
+        None => panic_with_error!(env, Error::NotNFT),
+    }
+    move_token(env, from, to, token_id);
+}
+
+/// Update the owner of `token_id` and everything following it, once the transfer is checked
+fn move_token(env: &Env, from: &Address, to: &Address, token_id: u32) {
+    if from != to {
+        #[cfg(feature = "enumerable")]
+        {
+            enumerable::remove_owner_token(env, from, token_id);
+            enumerable::add_owner_token(env, to, token_id);
+        }
+        #[cfg(not(feature = "enumerable"))]
+        {
+            sub_balance(env, from, 1);
+            add_balance(env, to, 1);
+        }
+        if cfg!(feature = "rentable") {
+            clear_user(env, token_id);
+        }
+        #[cfg(feature = "provenance")]
+        provenance::record(env, token_id, from, to);
+    }
+    #[cfg(feature = "votes")]
+    votes::move_voting_units(env, Some(from), Some(to), 1);
+    // Approvals never survive an ownership change
+    DataKey::Approved(token_id).remove(env);
+    DataKey::TokenOwner(token_id).set(env, to);
+}
+
+/// Approve `operator` for a single token, or clear the approval with `None`
+fn approve(
+    env: &Env,
+    caller: &Address,
+    operator: Option<&Address>,
+    token_id: u32,
+    expiration_ledger: u32,
+) {
+    let owner = consecutive::owner_of(env, token_id)
+        .unwrap_or_else(|| panic_with_error!(env, Error::NotNFT));
+    if owner != *caller && !approval::is_operator(env, &owner, caller) {
+        panic_with_error!(env, Error::NotAuthorized);
+    }
+    caller.require_auth();
+    #[cfg(feature = "regulated")]
+    {
+        regulated::check_token(env, token_id);
+        regulated::check_accounts(env, &[caller]);
+    }
+
+    if let Some(to_approve) = operator {
+        #[cfg(feature = "operator-filter")]
+        operator_filter::check(env, to_approve);
+        approval::set_approval(env, token_id, to_approve.clone(), expiration_ledger);
+    } else {
+        DataKey::Approved(token_id).remove(env);
     }
 }
 
+/// Grant or remove an operator of all the tokens of `owner`
+fn approve_for_all(
+    env: &Env,
+    caller: &Address,
+    owner: &Address,
+    operator: &Address,
+    approved: bool,
+    expiration_ledger: u32,
+) {
+    if owner != caller && !approval::is_operator(env, owner, caller) {
+        panic_with_error!(env, Error::NotAuthorized);
+    }
+    caller.require_auth();
+
+    if approved {
+        #[cfg(feature = "operator-filter")]
+        operator_filter::check(env, operator);
+        #[cfg(feature = "regulated")]
+        regulated::check_accounts(env, &[caller, owner]);
+        approval::set_operator(env, owner, operator, expiration_ledger);
+    } else {
+        DataKey::Operator(owner.clone(), operator.clone()).remove(env);
+    }
+}
+
+/// Destroy a token of `owner`, the caller is checked by the actual implementation
+#[cfg(feature = "burnable")]
+fn burn(env: &Env, owner: &Address, token_id: u32) {
+    // Soulbound tokens can still be burned, but not the ones pinned by a locker
+    #[cfg(feature = "lockable")]
+    if DataKeyLock::Locker(token_id).has(env) {
+        panic_with_error!(env, Error::TokenLocked);
+    }
+    #[cfg(feature = "regulated")]
+    {
+        regulated::check_token(env, token_id);
+        regulated::check_accounts(env, &[owner]);
+    }
+    // The children would be left without a root owner
+    #[cfg(feature = "nested")]
+    if !nested::children(env, &env.current_contract_address(), token_id).is_empty() {
+        panic_with_error!(env, Error::HasChildren);
+    }
+
+    DataKey::Approved(token_id).remove(env);
+    DataKey::TokenOwner(token_id).remove(env);
+    #[cfg(feature = "attributes")]
+    DataKeyAttributes::Token(token_id).remove(env);
+    // A token minted again with the same id starts afresh
+    #[cfg(feature = "metadata")]
+    {
+        DatakeyMetadata::Uri(token_id).remove(env);
+        DatakeyMetadata::Onchain(token_id).remove(env);
+        DatakeyMetadata::Frozen(token_id).remove(env);
+    }
+    consecutive::burn(env, token_id);
+    if cfg!(feature = "rentable") {
+        clear_user(env, token_id);
+    }
+    #[cfg(feature = "votes")]
+    votes::move_voting_units(env, Some(owner), None, 1);
+
+    #[cfg(feature = "enumerable")]
+    {
+        enumerable::remove_owner_token(env, owner, token_id);
+        enumerable::remove_token(env, token_id);
+    }
+    #[cfg(not(feature = "enumerable"))]
+    sub_balance(env, owner, 1);
+}
+
+/// Give `token_id` to `to` unless it already exists, return whether it was minted
+fn mint(env: &Env, to: &Address, token_id: u32) -> bool {
+    if consecutive::owner_of(env, token_id).is_some() {
+        return false;
+    }
+    DataKey::TokenOwner(token_id).set(env, to);
+    consecutive::reserve(env, token_id);
+    #[cfg(feature = "provenance")]
+    provenance::record(env, token_id, &env.current_contract_address(), to);
+
+    #[cfg(feature = "enumerable")]
+    {
+        enumerable::add_token(env, token_id);
+        enumerable::add_owner_token(env, to, token_id);
+    }
+    #[cfg(not(feature = "enumerable"))]
+    add_balance(env, to, 1);
+    #[cfg(feature = "votes")]
+    votes::move_voting_units(env, None, Some(to), 1);
+    true
+}
+
+/// Publish the events of a new token
+fn publish_mint(env: &Env, to: &Address, token_id: u32) {
+    let mut v: Vec<Val> = Vec::new(env);
+    v.push_back(to.into_val(env));
+    v.push_back(token_id.into());
+    Event::Mint.publish(env, v);
+
+    // ERC5192: soulbound tokens are locked from the start
+    if cfg!(feature = "soulbound") {
+        let v: Val = token_id.into();
+        Event::Locked.publish(env, v);
+    }
+}
+
+/// Store the admin, once
+fn init(env: &Env, admin: &Address) {
+    if Admin::User.has(env) {
+        panic!("Already initialized")
+    }
+    Admin::User.set(env, admin);
+    env.storage().instance().extend_ttl(10000, 10000);
+}
+
+/// Add `amount` to the balance of `owner`, the enumerable indexes do it themselves
+#[cfg(not(feature = "enumerable"))]
+fn add_balance(env: &Env, owner: &Address, amount: u32) {
+    let key = DataKey::Balance(owner.clone());
+    let balance: u32 = key.get(env).unwrap_or(0);
+    let balance = balance
+        .checked_add(amount)
+        .unwrap_or_else(|| panic_with_error!(env, Error::OutOfBounds));
+    key.set(env, &balance);
+}
+
+/// Remove `amount` from the balance of `owner`, which must hold at least that many tokens
+#[cfg(not(feature = "enumerable"))]
+fn sub_balance(env: &Env, owner: &Address, amount: u32) {
+    let key = DataKey::Balance(owner.clone());
+    let balance: u32 = key.get(env).unwrap_or(0);
+    let balance = balance
+        .checked_sub(amount)
+        .unwrap_or_else(|| panic_with_error!(env, Error::NotOwner));
+    key.set(env, &balance);
+}
+
+/// Remove the user of a rented token, the rental does not survive an ownership change
+fn clear_user(env: &Env, token_id: u32) {
+    let key = DataKeyRentable::User(token_id);
+    if key.has(env) {
+        key.remove(env);
+        Event::UpdateUser.publish(env, (token_id, Option::<Address>::None, 0u32));
+    }
+}
+
+/// Check if the spender is the owner, the approved address or an operator of the owner
+#[cfg(feature = "rentable")]
+fn is_approved_or_owner(env: &Env, owner: &Address, spender: &Address, token_id: u32) -> bool {
+    owner == spender
+        || approval::approved(env, token_id).as_ref() == Some(spender)
+        || approval::is_operator(env, owner, spender)
+}
+
+/// Check the enumerable indexes are consistent, see `enumerable::check_invariants`
+#[cfg(all(feature = "enumerable", any(test, feature = "testutils")))]
+pub fn check_enumerable_invariants(env: &Env) -> bool {
+    enumerable::check_invariants(env)
+}
+
+/// Require the authorization of `account`, the owner of `token_id` or one of
+/// its delegates, for the actions open to hot wallets
+#[cfg(feature = "delegation")]
+pub fn require_owner_or_delegate(env: &Env, account: &Address, token_id: u32) {
+    if !delegation::is_owner_or_delegate(env, account, token_id) {
+        panic_with_error!(env, Error::NotAuthorized);
+    }
+    account.require_auth();
+}
+
+/// The lowest id above every token minted, free for the next mint
+pub fn next_token_id(env: &Env) -> u32 {
+    consecutive::next_id(env)
+}
+
 pub fn get_admin(env: &Env) -> Address {
     if let Some(addr) = Admin::User.get(env) {
         addr
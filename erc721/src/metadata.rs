@@ -0,0 +1,114 @@
+use crate::{DatakeyMetadata, Error};
+#[cfg(all(feature = "onchain-metadata", feature = "attributes"))]
+use soroban_sdk::TryFromVal;
+use soroban_sdk::{panic_with_error, Env, String};
+use storage::Storage;
+
+/// Longest uri that can be assembled from the base uri and a token id
+pub const MAX_URI_LEN: usize = 256;
+
+/// Resolve the uri of a token: the per-token uri if set, then the on-chain
+/// metadata as a data uri, otherwise the base uri followed by the token id in
+/// decimal.
+pub fn token_uri(env: &Env, token_id: u32) -> Option<String> {
+    if let Some(uri) = DatakeyMetadata::Uri(token_id).get(env) {
+        return Some(uri);
+    }
+    #[cfg(feature = "onchain-metadata")]
+    if let Some(metadata) = DatakeyMetadata::Onchain(token_id).get(env) {
+        return Some(onchain_uri(env, token_id, &metadata));
+    }
+    let base: String = DatakeyMetadata::BaseUri.get(env)?;
+
+    check_base_uri(env, &base);
+    let len = base.len() as usize;
+    let mut buf = [0u8; MAX_URI_LEN];
+    base.copy_into_slice(&mut buf[..len]);
+    let end = len + write_decimal(&mut buf[len..], token_id);
+    Some(String::from_bytes(env, &buf[..end]))
+}
+
+/// Check a token id can be appended to the base uri, a u32 takes at most 10 digits
+pub fn check_base_uri(env: &Env, base: &String) {
+    if base.len() as usize + 10 > MAX_URI_LEN {
+        panic_with_error!(env, Error::UriTooLong);
+    }
+}
+
+/// Whether the uri and attributes of `token_id` can no longer change
+pub fn is_frozen(env: &Env, token_id: u32) -> bool {
+    DatakeyMetadata::FrozenAll.has(env) || DatakeyMetadata::Frozen(token_id).has(env)
+}
+
+pub fn check_token(env: &Env, token_id: u32) {
+    if is_frozen(env, token_id) {
+        panic_with_error!(env, Error::MetadataFrozen);
+    }
+}
+
+/// Fail once the metadata of the whole collection is frozen
+pub fn check_all(env: &Env) {
+    if DatakeyMetadata::FrozenAll.has(env) {
+        panic_with_error!(env, Error::MetadataFrozen);
+    }
+}
+
+/// Write the decimal representation of `n` and return the number of bytes written
+pub fn write_decimal(out: &mut [u8], mut n: u32) -> usize {
+    let mut digits = [0u8; 10];
+    let mut i = digits.len();
+    loop {
+        i -= 1;
+        digits[i] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+    let len = digits.len() - i;
+    out[..len].copy_from_slice(&digits[i..]);
+    len
+}
+
+/// Build the `data:application/json;base64,` uri of on-chain metadata, the
+/// stored attributes of the token follow the ones of `metadata`
+#[cfg(feature = "onchain-metadata")]
+pub fn onchain_uri(env: &Env, token_id: u32, metadata: &crate::TokenMetadata) -> String {
+    let mut json = crate::json::JsonBuilder::new();
+    json.begin_object()
+        .key("name")
+        .string(&metadata.name)
+        .key("description")
+        .string(&metadata.description)
+        .key("image")
+        .string(&metadata.image)
+        .key("attributes")
+        .begin_array();
+    for attribute in metadata.attributes.iter() {
+        json.begin_object()
+            .key("trait_type")
+            .string(&attribute.trait_type)
+            .key("value")
+            .string(&attribute.value)
+            .end_object();
+    }
+    #[cfg(feature = "attributes")]
+    for (name, value) in crate::attributes::get(env, &crate::DataKeyAttributes::Token(token_id)) {
+        let name = soroban_sdk::SymbolStr::try_from_val(env, &name.to_symbol_val())
+            .unwrap_or_else(|_| panic_with_error!(env, Error::MetadataTooLarge));
+        json.begin_object()
+            .key("trait_type")
+            .str(name.as_ref())
+            .key("value");
+        match value {
+            crate::AttributeValue::String(value) => json.string(&value),
+            crate::AttributeValue::U32(value) => json.u32(value),
+            crate::AttributeValue::I128(value) => json.i128(value),
+        };
+        json.end_object();
+    }
+    #[cfg(not(feature = "attributes"))]
+    let _ = token_id;
+    json.end_array().end_object();
+    json.to_data_uri(env)
+}
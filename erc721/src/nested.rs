@@ -0,0 +1,111 @@
+//! Tokens owned by other tokens (ERC998 top-down).
+//!
+//! A nested token is held by the contract itself and records its parent, a
+//! token of this collection or of another one. Its root owner, found by walking
+//! up the parents, is the only one able to take it out, so the whole tree moves
+//! with the root token.
+use crate::{consecutive, introspection, DataKeyNested, Error, ParentToken};
+use soroban_sdk::{contractclient, panic_with_error, Address, Env, Symbol, Vec};
+use storage::Storage;
+
+/// Longest chain of parents, the walk up to the root is bounded by it
+pub const MAX_DEPTH: u32 = 8;
+
+/// What is called on a parent living in another collection
+#[contractclient(name = "ParentClient")]
+pub trait ParentCollection {
+    fn owner_of(env: Env, token_id: u32) -> Address;
+    fn root_owner_of(env: Env, token_id: u32) -> Address;
+    fn supports_interface(env: Env, interface_id: Symbol) -> bool;
+}
+
+pub fn parent(env: &Env, token_id: u32) -> Option<ParentToken> {
+    DataKeyNested::Parent(token_id).get(env)
+}
+
+pub fn children(env: &Env, contract: &Address, token_id: u32) -> Vec<u32> {
+    DataKeyNested::Children(contract.clone(), token_id)
+        .get(env)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn root_owner(env: &Env, token_id: u32) -> Address {
+    walk(
+        env,
+        env.current_contract_address(),
+        token_id,
+        None,
+        MAX_DEPTH + 1,
+    )
+}
+
+/// Check `parent` exists and can receive `token_id`: it must not be one of the
+/// token's descendants and the chain above it must leave room for one more level
+pub fn check_parent(env: &Env, token_id: u32, parent: &ParentToken) {
+    walk(
+        env,
+        parent.contract.clone(),
+        parent.token_id,
+        Some(token_id),
+        MAX_DEPTH,
+    );
+}
+
+pub fn attach(env: &Env, token_id: u32, parent: &ParentToken) {
+    DataKeyNested::Parent(token_id).set(env, parent);
+    let mut siblings = children(env, &parent.contract, parent.token_id);
+    siblings.push_back(token_id);
+    DataKeyNested::Children(parent.contract.clone(), parent.token_id).set(env, &siblings);
+}
+
+pub fn detach(env: &Env, token_id: u32, parent: &ParentToken) {
+    DataKeyNested::Parent(token_id).remove(env);
+    let key = DataKeyNested::Children(parent.contract.clone(), parent.token_id);
+    let mut siblings = children(env, &parent.contract, parent.token_id);
+    if let Some(index) = siblings.first_index_of(token_id) {
+        siblings.remove(index);
+    }
+    if siblings.is_empty() {
+        key.remove(env);
+    } else {
+        key.set(env, &siblings);
+    }
+}
+
+/// Follow the parents from `token_id` of `contract` up to an address. Loops can
+/// only be made within this collection, the host rejects a contract calling
+/// itself back through another one.
+fn walk(
+    env: &Env,
+    mut contract: Address,
+    mut token_id: u32,
+    child: Option<u32>,
+    depth: u32,
+) -> Address {
+    let this = env.current_contract_address();
+    for _ in 0..depth {
+        if contract != this {
+            // Collections without nesting still answer `owner_of`
+            let client = ParentClient::new(env, &contract);
+            let interface_id = Symbol::new(env, introspection::NESTED);
+            return match client.try_supports_interface(&interface_id) {
+                Ok(Ok(true)) => client.root_owner_of(&token_id),
+                _ => client.owner_of(&token_id),
+            };
+        }
+        if child == Some(token_id) {
+            panic_with_error!(env, Error::NestingLoop);
+        }
+        match parent(env, token_id) {
+            Some(parent) => {
+                contract = parent.contract;
+                token_id = parent.token_id;
+            }
+            None => {
+                return consecutive::owner_of(env, token_id)
+                    .unwrap_or_else(|| panic_with_error!(env, Error::NotNFT))
+            }
+        }
+    }
+    panic_with_error!(env, Error::NestingLoop)
+}
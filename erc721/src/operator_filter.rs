@@ -0,0 +1,38 @@
+//! Restrict the addresses acting on the tokens of others, e.g. to the
+//! marketplaces honoring royalties.
+//!
+//! The filter is checked when an operator is approved and each time one
+//! transfers a token, so tightening it also disables the existing approvals.
+//! Owners can always revoke.
+use crate::{DataKeyOperatorFilter, Error, OperatorFilter};
+use soroban_sdk::{contractclient, panic_with_error, Address, Env};
+use storage::Storage;
+
+/// A shared registry, asked about the operators of each `collection`
+#[contractclient(name = "OperatorRegistryClient")]
+pub trait OperatorRegistry {
+    fn is_operator_allowed(env: Env, collection: Address, operator: Address) -> bool;
+}
+
+pub fn filter(env: &Env) -> OperatorFilter {
+    DataKeyOperatorFilter::Filter
+        .get(env)
+        .unwrap_or(OperatorFilter::Open)
+}
+
+pub fn is_allowed(env: &Env, operator: &Address) -> bool {
+    match filter(env) {
+        OperatorFilter::Open => true,
+        OperatorFilter::Allowlist => DataKeyOperatorFilter::Allowed(operator.clone()).has(env),
+        OperatorFilter::Denylist => !DataKeyOperatorFilter::Denied(operator.clone()).has(env),
+        // A failing registry fails the call rather than letting everyone through
+        OperatorFilter::Registry(registry) => OperatorRegistryClient::new(env, &registry)
+            .is_operator_allowed(&env.current_contract_address(), operator),
+    }
+}
+
+pub fn check(env: &Env, operator: &Address) {
+    if !is_allowed(env, operator) {
+        panic_with_error!(env, Error::OperatorNotAllowed);
+    }
+}
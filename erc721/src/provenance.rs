@@ -0,0 +1,86 @@
+//! Ownership history of each token, kept on-chain in a ring buffer holding
+//! the latest `length` mints and transfers. The buffer is a single entry,
+//! rewritten whole on each change, `MAX_LENGTH` bounds its size.
+use crate::{consecutive, DataKeyProvenance, Error, ProvenanceEntry, ProvenanceLog};
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
+use storage::Storage;
+
+pub const DEFAULT_LENGTH: u32 = 16;
+pub const MAX_LENGTH: u32 = 64;
+
+pub fn length(env: &Env) -> u32 {
+    DataKeyProvenance::Length.get(env).unwrap_or(DEFAULT_LENGTH)
+}
+
+pub fn set_length(env: &Env, length: u32) {
+    if length == 0 || length > MAX_LENGTH {
+        panic_with_error!(env, Error::OutOfBounds);
+    }
+    DataKeyProvenance::Length.set(env, &length);
+}
+
+/// Record an ownership change of `token_id`, overwriting the oldest entry when full
+pub fn record(env: &Env, token_id: u32, from: &Address, to: &Address) {
+    let entry = ProvenanceEntry {
+        from: from.clone(),
+        to: to.clone(),
+        ledger: env.ledger().sequence(),
+    };
+    let capacity = length(env);
+    let mut log = log(env, token_id);
+
+    // The length changed since the buffer wrapped, start again from the latest entries
+    let len = log.entries.len();
+    if len > capacity || (log.head != 0 && len != capacity) {
+        let ordered = ordered(&log);
+        let skip = ordered.len().saturating_sub(capacity);
+        log = ProvenanceLog {
+            head: 0,
+            entries: ordered.slice(skip..),
+        };
+    }
+    if log.entries.len() < capacity {
+        log.entries.push_back(entry);
+    } else {
+        log.entries.set(log.head, entry);
+        log.head = (log.head + 1) % capacity;
+    }
+    DataKeyProvenance::Log(token_id).set(env, &log);
+}
+
+/// Entries of `token_id`, oldest first
+pub fn entries(env: &Env, token_id: u32) -> Vec<ProvenanceEntry> {
+    ordered(&log(env, token_id))
+}
+
+/// The log of `token_id`. A consecutive batch records no log for its tokens,
+/// their mint entry is resolved from the batch until they first move.
+fn log(env: &Env, token_id: u32) -> ProvenanceLog {
+    DataKeyProvenance::Log(token_id)
+        .get(env)
+        .unwrap_or_else(|| {
+            let mut entries = Vec::new(env);
+            if let Some((to, ledger)) = consecutive::batch(env, token_id) {
+                entries.push_back(ProvenanceEntry {
+                    from: env.current_contract_address(),
+                    to,
+                    ledger,
+                });
+            }
+            ProvenanceLog { head: 0, entries }
+        })
+}
+
+fn ordered(log: &ProvenanceLog) -> Vec<ProvenanceEntry> {
+    let mut entries = log.entries.slice(log.head..);
+    entries.append(&log.entries.slice(..log.head));
+    entries
+}
+
+/// Read up to `limit` entries of `token_id` from the `start`-th oldest one
+pub fn page(env: &Env, token_id: u32, start: u32, limit: u32) -> Vec<ProvenanceEntry> {
+    let entries = entries(env, token_id);
+    let start = start.min(entries.len());
+    let end = start.saturating_add(limit).min(entries.len());
+    entries.slice(start..end)
+}
@@ -0,0 +1,42 @@
+//! Compliance controls held by a regulator, a role set by the admin.
+//!
+//! Frozen tokens can't be transferred, approved or burned, frozen accounts
+//! can neither send, receive nor act on the tokens of others. The regulator
+//! takes a token back with `clawback`, whatever its owner, freezes and locks.
+use crate::{DataKeyRegulated, Error};
+use soroban_sdk::{panic_with_error, Address, Env};
+use storage::Storage;
+
+pub fn regulator(env: &Env) -> Option<Address> {
+    DataKeyRegulated::Regulator.get(env)
+}
+
+/// Require the authorization of the regulator, there is none until the admin sets it
+pub fn require_regulator(env: &Env) {
+    regulator(env)
+        .unwrap_or_else(|| panic_with_error!(env, Error::NotAuthorized))
+        .require_auth();
+}
+
+pub fn is_token_frozen(env: &Env, token_id: u32) -> bool {
+    DataKeyRegulated::FrozenToken(token_id).has(env)
+}
+
+pub fn is_account_frozen(env: &Env, account: &Address) -> bool {
+    DataKeyRegulated::FrozenAccount(account.clone()).has(env)
+}
+
+pub fn check_token(env: &Env, token_id: u32) {
+    if is_token_frozen(env, token_id) {
+        panic_with_error!(env, Error::Frozen);
+    }
+}
+
+pub fn check_accounts(env: &Env, accounts: &[&Address]) {
+    if accounts
+        .iter()
+        .any(|account| is_account_frozen(env, account))
+    {
+        panic_with_error!(env, Error::Frozen);
+    }
+}
@@ -0,0 +1,137 @@
+//! SEP-0050 interface on top of the erc721 storage.
+//!
+//! The function names clash with the ERC721 ones (`approve` takes different
+//! arguments), so the adapter is a separate struct. A collection exposes one
+//! interface or the other, and can switch by upgrading its wasm since both
+//! read and write the same entries.
+use crate::{approval, ERC721Contract, NonFungibleToken, ERC721};
+#[cfg(feature = "metadata")]
+use crate::{ERC721Metadata, NonFungibleMetadata};
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
+#[cfg(feature = "burnable")]
+use {
+    crate::{consecutive, Error, NonFungibleBurnable},
+    soroban_sdk::panic_with_error,
+};
+
+#[cfg_attr(test, soroban_sdk::contract)]
+pub struct SEP50Contract;
+
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl NonFungibleToken for SEP50Contract {
+    fn balance(env: Env, account: Address) -> u32 {
+        ERC721Contract::balance_of(env, account)
+    }
+    fn owner_of(env: Env, token_id: u32) -> Address {
+        ERC721Contract::owner_of(env, token_id)
+    }
+    fn transfer(env: Env, from: Address, to: Address, token_id: u32) {
+        crate::transfer(&env, &from, &from, &to, token_id);
+        publish_transfer(&env, from, to, token_id);
+    }
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, token_id: u32) {
+        crate::transfer(&env, &spender, &from, &to, token_id);
+        publish_transfer(&env, from, to, token_id);
+    }
+    fn approve(
+        env: Env,
+        approver: Address,
+        approved: Address,
+        token_id: u32,
+        live_until_ledger: u32,
+    ) {
+        // A zero ledger revokes the approval
+        let operator = (live_until_ledger != 0).then_some(&approved);
+        crate::approve(&env, &approver, operator, token_id, live_until_ledger);
+        env.events().publish(
+            (symbol_short!("approve"), approver, token_id),
+            (approved, live_until_ledger),
+        );
+    }
+    fn approve_for_all(env: Env, owner: Address, operator: Address, live_until_ledger: u32) {
+        // Only the owner manages its operators here
+        crate::approve_for_all(
+            &env,
+            &owner,
+            &owner,
+            &operator,
+            live_until_ledger != 0,
+            live_until_ledger,
+        );
+        env.events().publish(
+            (Symbol::new(&env, "approve_for_all"), owner),
+            (operator, live_until_ledger),
+        );
+    }
+    fn get_approved(env: Env, token_id: u32) -> Option<Address> {
+        approval::approved(&env, token_id)
+    }
+    fn is_approved_for_all(env: Env, owner: Address, operator: Address) -> bool {
+        approval::is_operator(&env, &owner, &operator)
+    }
+}
+
+#[cfg(feature = "metadata")]
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl NonFungibleMetadata for SEP50Contract {
+    fn name(env: Env) -> soroban_sdk::String {
+        <ERC721Contract as ERC721Metadata>::name(env)
+    }
+    fn symbol(env: Env) -> soroban_sdk::String {
+        <ERC721Contract as ERC721Metadata>::symbol(env)
+    }
+    fn token_uri(env: Env, token_id: u32) -> soroban_sdk::String {
+        <ERC721Contract as ERC721Metadata>::token_uri(env, token_id)
+    }
+}
+
+#[cfg(feature = "burnable")]
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl NonFungibleBurnable for SEP50Contract {
+    fn burn(env: Env, from: Address, token_id: u32) {
+        from.require_auth();
+        check_owner(&env, &from, token_id);
+        crate::burn(&env, &from, token_id);
+        env.events()
+            .publish((symbol_short!("burn"), from), token_id);
+    }
+    fn burn_from(env: Env, spender: Address, from: Address, token_id: u32) {
+        spender.require_auth();
+        check_owner(&env, &from, token_id);
+        let is_spender_approved = spender == from
+            || approval::approved(&env, token_id).as_ref() == Some(&spender)
+            || approval::is_operator(&env, &from, &spender);
+        if !is_spender_approved {
+            panic_with_error!(&env, Error::NotAuthorized);
+        }
+        crate::burn(&env, &from, token_id);
+        env.events()
+            .publish((symbol_short!("burn"), from), token_id);
+    }
+}
+
+#[cfg_attr(test, soroban_sdk::contractimpl)]
+impl SEP50Contract {
+    /// Mint a token and publish the SEP-0050 `mint` event
+    pub fn mint(env: Env, to: Address, token_id: u32) {
+        // Authorization should be handled by the caller of the actual implementation
+
+        if crate::mint(&env, &to, token_id) {
+            env.events().publish((symbol_short!("mint"), to), token_id);
+        }
+    }
+}
+
+fn publish_transfer(env: &Env, from: Address, to: Address, token_id: u32) {
+    env.events()
+        .publish((symbol_short!("transfer"), from, to), token_id);
+}
+
+#[cfg(feature = "burnable")]
+fn check_owner(env: &Env, from: &Address, token_id: u32) {
+    match consecutive::owner_of(env, token_id) {
+        Some(owner) if owner == *from => {}
+        Some(_) => panic_with_error!(env, Error::NotOwner),
+        None => panic_with_error!(env, Error::NotNFT),
+    }
+}
@@ -2,21 +2,44 @@
 extern crate std;
 
 use super::*;
-use soroban_sdk::testutils::{Address as _, MockAuth, MockAuthInvoke};
-use soroban_sdk::{vec, Address, String};
+use soroban_sdk::testutils::Address as _;
+#[cfg(feature = "enumerable")]
+use soroban_sdk::vec;
+use soroban_sdk::Address;
+#[cfg(feature = "metadata")]
+use soroban_sdk::String;
 
-#[test]
-fn simpl_test() {
-    let env = Env::default();
+/// Register and initialize a fresh contract, whatever the enabled features
+fn setup(env: &Env) -> ERC721ContractClient<'_> {
     let contract_id = env.register_contract(None, ERC721Contract);
-    let client = ERC721ContractClient::new(&env, &contract_id);
+    let client = ERC721ContractClient::new(env, &contract_id);
 
-    let admin = Address::generate(&env);
+    let admin = Address::generate(env);
     client.initialize(
         &admin,
-        &String::from_str(&env, "Cool NFT"),
-        &String::from_str(&env, "COOL"),
+        #[cfg(feature = "metadata")]
+        &String::from_str(env, "Cool NFT"),
+        #[cfg(feature = "metadata")]
+        &String::from_str(env, "COOL"),
     );
+    client
+}
+
+/// Run `f` as the contract for a test expecting it to fail. A contract panic
+/// can't unwind through the client, it would abort the whole test process.
+fn reject(client: &ERC721ContractClient, f: impl FnOnce()) {
+    client.env.as_contract(&client.address, f);
+}
+
+#[test]
+#[cfg(all(
+    feature = "enumerable",
+    feature = "burnable",
+    not(feature = "soulbound")
+))]
+fn simpl_test() {
+    let env = Env::default();
+    let client = setup(&env);
 
     let user1 = Address::generate(&env);
     client.mock_all_auths().mint(&user1, &1);
@@ -59,3 +82,1383 @@ fn simpl_test() {
     assert_eq!(client.token_of_owner_by_index(&user2, &0), 3);
     assert_eq!(client.total_supply(), 2);
 }
+
+#[test]
+#[cfg(all(feature = "lockable", not(feature = "soulbound")))]
+fn lock_test() {
+    let env = Env::default();
+    let client = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    client.mock_all_auths().mint(&user1, &1);
+    assert!(!client.locked(&1));
+
+    client.mock_all_auths().lock(&user1, &user1, &1);
+    assert!(client.locked(&1));
+
+    client.mock_all_auths().unlock(&user1, &1);
+    assert!(!client.locked(&1));
+    client
+        .mock_all_auths()
+        .transfer_from(&user1, &user1, &user2, &1);
+    assert_eq!(client.balance_of(&user2), 1);
+
+    // An escrow pins the token without taking custody and releases it
+    let escrow = Address::generate(&env);
+    client.mock_all_auths().lock(&user2, &escrow, &1);
+    assert!(client.locked(&1));
+    assert_eq!(client.owner_of(&1), user2);
+    client.mock_all_auths().unlock(&escrow, &1);
+    assert!(!client.locked(&1));
+}
+
+#[cfg(all(feature = "lockable", not(feature = "soulbound")))]
+fn escrow_lock_setup(env: &Env) -> (ERC721ContractClient<'_>, Address) {
+    env.mock_all_auths();
+    let client = setup(env);
+    let user1 = Address::generate(env);
+    let escrow = Address::generate(env);
+    client.mint(&user1, &1);
+    client.lock(&user1, &escrow, &1);
+    (client, user1)
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+#[cfg(all(feature = "lockable", not(feature = "soulbound")))]
+fn escrow_lock_transfer_test() {
+    let env = Env::default();
+    let (client, user1) = escrow_lock_setup(&env);
+
+    let user2 = Address::generate(&env);
+    reject(&client, || {
+        ERC721Contract::transfer_from(env.clone(), user1.clone(), user1, user2, 1)
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+#[cfg(all(feature = "lockable", not(feature = "soulbound")))]
+fn escrow_lock_unlock_test() {
+    let env = Env::default();
+    let (client, user1) = escrow_lock_setup(&env);
+
+    // Only the escrow can release the token, not its owner
+    reject(&client, || ERC721Contract::unlock(env.clone(), user1, 1));
+}
+
+#[test]
+#[cfg(all(feature = "soulbound", feature = "burnable"))]
+fn soulbound_test() {
+    use soroban_sdk::testutils::Events;
+    use soroban_sdk::{vec, IntoVal, String};
+
+    let env = Env::default();
+    let client = setup(&env);
+
+    let user1 = Address::generate(&env);
+    client.mock_all_auths().mint(&user1, &1);
+    assert!(client.locked(&1));
+
+    // ERC5192: the token is announced as locked when minted
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (String::from_str(&env, "Locked"),).into_val(&env),
+                1u32.into_val(&env)
+            )
+        ]
+    );
+
+    client.mock_all_auths().burn(&user1, &1);
+    assert_eq!(client.balance_of(&user1), 0);
+}
+
+#[test]
+#[cfg(all(feature = "rentable", not(feature = "soulbound")))]
+fn rentable_test() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let renter = Address::generate(&env);
+    client.mock_all_auths().mint(&owner, &1);
+    assert_eq!(client.user_of(&1), None);
+
+    client
+        .mock_all_auths()
+        .set_user(&owner, &1, &Some(renter.clone()), &100);
+    assert_eq!(client.user_of(&1), Some(renter.clone()));
+    assert_eq!(client.user_expires(&1), 100);
+
+    // The rental lapses by itself
+    env.ledger().with_mut(|l| l.sequence_number = 101);
+    assert_eq!(client.user_of(&1), None);
+
+    // And is cleared when the token changes hands
+    client
+        .mock_all_auths()
+        .set_user(&owner, &1, &Some(renter.clone()), &200);
+    client
+        .mock_all_auths()
+        .transfer_from(&owner, &owner, &renter, &1);
+    assert_eq!(client.user_of(&1), None);
+    assert_eq!(client.user_expires(&1), 0);
+}
+
+#[test]
+#[cfg(not(feature = "soulbound"))]
+fn consecutive_test() {
+    let env = Env::default();
+    let client = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    client.mock_all_auths().mint(&user2, &3);
+
+    // The batch starts after the highest token id already minted
+    assert_eq!(client.mock_all_auths().mint_consecutive(&user1, &4), 4);
+    assert_eq!(client.balance_of(&user1), 4);
+    assert_eq!(client.owner_of(&4), user1);
+    assert_eq!(client.owner_of(&7), user1);
+    #[cfg(feature = "enumerable")]
+    assert_eq!(client.total_supply(), 5);
+
+    client
+        .mock_all_auths()
+        .transfer_from(&user1, &user1, &user2, &5);
+    assert_eq!(client.owner_of(&5), user2);
+    assert_eq!(client.owner_of(&6), user1);
+    assert_eq!(client.balance_of(&user1), 3);
+    assert_eq!(client.balance_of(&user2), 2);
+
+    #[cfg(feature = "burnable")]
+    {
+        client.mock_all_auths().burn(&user1, &6);
+        assert_eq!(client.balance_of(&user1), 2);
+    }
+
+    // Next batch follows the previous one
+    assert_eq!(client.mock_all_auths().mint_consecutive(&user2, &2), 8);
+    assert_eq!(client.owner_of(&9), user2);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")]
+fn consecutive_cap_test() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let user1 = Address::generate(&env);
+    reject(&client, || {
+        ERC721Contract::mint_consecutive(env.clone(), user1, MAX_CONSECUTIVE + 1);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")]
+fn mint_last_id_test() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    // No id is left after it for the next batch
+    let user1 = Address::generate(&env);
+    reject(&client, || {
+        ERC721Contract::mint(env.clone(), user1, u32::MAX)
+    });
+}
+
+#[test]
+fn airdrop_test() {
+    let env = Env::default();
+    let client = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let mut recipients = Vec::new(&env);
+    for token_id in 0..MAX_AIRDROP_SIZE + 2 {
+        let to = if token_id % 2 == 0 { &user1 } else { &user2 };
+        recipients.push_back((to.clone(), token_id));
+    }
+    // Id 0 is listed for user1 but owned by user2, id 1 is already user2's
+    client.mock_all_auths().mint(&user2, &0);
+    client.mock_all_auths().mint(&user2, &1);
+
+    // Cut at the batch size, the conflicting token is left untouched
+    let summary = client.mock_all_auths().airdrop(&recipients);
+    assert_eq!(
+        summary,
+        AirdropSummary {
+            minted: MAX_AIRDROP_SIZE - 2,
+            skipped: 1,
+            conflicts: 1,
+            next: MAX_AIRDROP_SIZE,
+        }
+    );
+    assert_eq!(client.owner_of(&0), user2);
+
+    // The next call resumes from the summary
+    let summary = client
+        .mock_all_auths()
+        .airdrop(&recipients.slice(summary.next..));
+    assert_eq!(
+        summary,
+        AirdropSummary {
+            minted: 2,
+            skipped: 0,
+            conflicts: 0,
+            next: 2,
+        }
+    );
+    assert_eq!(client.owner_of(&(MAX_AIRDROP_SIZE + 1)), user2);
+
+    // The whole batch sent again mints nothing
+    let summary = client.mock_all_auths().airdrop(&recipients);
+    assert_eq!(
+        summary,
+        AirdropSummary {
+            minted: 0,
+            skipped: MAX_AIRDROP_SIZE - 1,
+            conflicts: 1,
+            next: MAX_AIRDROP_SIZE,
+        }
+    );
+    assert_eq!(
+        client.balance_of(&user1) + client.balance_of(&user2),
+        MAX_AIRDROP_SIZE + 2
+    );
+}
+
+#[test]
+#[cfg(not(feature = "soulbound"))]
+fn balance_test() {
+    // Runs under every feature combination, with or without the enumerable indexes
+    let env = Env::default();
+    let client = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    client.mock_all_auths().mint(&user1, &1);
+    client.mock_all_auths().mint(&user1, &2);
+    assert_eq!(client.balance_of(&user1), 2);
+    assert_eq!(client.balance_of(&user2), 0);
+
+    client
+        .mock_all_auths()
+        .transfer_from(&user1, &user1, &user2, &1);
+    assert_eq!(client.balance_of(&user1), 1);
+    assert_eq!(client.balance_of(&user2), 1);
+
+    // Transferring to oneself leaves the balance untouched
+    client
+        .mock_all_auths()
+        .transfer_from(&user2, &user2, &user2, &1);
+    assert_eq!(client.balance_of(&user2), 1);
+
+    #[cfg(feature = "burnable")]
+    {
+        client.mock_all_auths().burn(&user2, &1);
+        client.mock_all_auths().burn(&user1, &2);
+        assert_eq!(client.balance_of(&user1), 0);
+        assert_eq!(client.balance_of(&user2), 0);
+    }
+}
+
+#[test]
+#[cfg(all(feature = "votes", feature = "burnable", not(feature = "soulbound")))]
+fn votes_test() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    let client = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    env.ledger().with_mut(|l| l.sequence_number = 10);
+    client.mock_all_auths().mint(&user1, &1);
+    client.mock_all_auths().mint(&user1, &2);
+
+    // Tokens only count once delegated
+    assert_eq!(client.get_votes(&user1), 0);
+    client.mock_all_auths().delegate(&user1, &user1);
+    assert_eq!(client.delegates(&user1), Some(user1.clone()));
+    assert_eq!(client.get_votes(&user1), 2);
+
+    env.ledger().with_mut(|l| l.sequence_number = 20);
+    client.mock_all_auths().delegate(&user2, &user2);
+    client
+        .mock_all_auths()
+        .transfer_from(&user1, &user1, &user2, &1);
+    assert_eq!(client.get_votes(&user1), 1);
+    assert_eq!(client.get_votes(&user2), 1);
+
+    env.ledger().with_mut(|l| l.sequence_number = 30);
+    client.mock_all_auths().burn(&user2, &1);
+    assert_eq!(client.get_votes(&user2), 0);
+
+    env.ledger().with_mut(|l| l.sequence_number = 40);
+    assert_eq!(client.get_past_votes(&user1, &9), 0);
+    assert_eq!(client.get_past_votes(&user1, &15), 2);
+    assert_eq!(client.get_past_votes(&user1, &25), 1);
+    assert_eq!(client.get_past_votes(&user2, &25), 1);
+    assert_eq!(client.get_past_votes(&user2, &35), 0);
+    assert_eq!(client.get_past_total_supply(&15), 2);
+    assert_eq!(client.get_past_total_supply(&35), 1);
+}
+
+#[test]
+#[cfg(feature = "metadata")]
+fn metadata_test() {
+    let env = Env::default();
+    let client = setup(&env);
+
+    let user1 = Address::generate(&env);
+    client.mock_all_auths().mint(&user1, &1);
+    client.mock_all_auths().mint(&user1, &42);
+    assert_eq!(client.token_uri(&1), String::from_str(&env, "no uri"));
+
+    client.set_base_uri(&String::from_str(&env, "ipfs://cid/"));
+    assert_eq!(client.token_uri(&1), String::from_str(&env, "ipfs://cid/1"));
+    assert_eq!(
+        client.token_uri(&42),
+        String::from_str(&env, "ipfs://cid/42")
+    );
+
+    // A token uri overrides the base uri
+    client.set_token_uri(&42, &String::from_str(&env, "ar://42"));
+    assert_eq!(client.token_uri(&42), String::from_str(&env, "ar://42"));
+    assert_eq!(client.token_uri(&1), String::from_str(&env, "ipfs://cid/1"));
+
+    client.set_contract_uri(&String::from_str(&env, "ipfs://cid/collection.json"));
+    assert_eq!(
+        client.contract_uri(),
+        String::from_str(&env, "ipfs://cid/collection.json")
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #9)")]
+#[cfg(feature = "metadata")]
+fn base_uri_too_long_test() {
+    let env = Env::default();
+    let client = setup(&env);
+
+    // Rejected when set rather than on every `token_uri`
+    let uri = String::from_bytes(&env, &[b'a'; metadata::MAX_URI_LEN - 9]);
+    reject(&client, || ERC721Contract::set_base_uri(env.clone(), uri));
+}
+
+#[test]
+#[cfg(feature = "metadata")]
+fn freeze_metadata_test() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let user1 = Address::generate(&env);
+    client.mint(&user1, &1);
+    client.mint(&user1, &2);
+    client.set_base_uri(&String::from_str(&env, "ipfs://v1/"));
+
+    // The frozen token keeps the uri it had
+    client.freeze_metadata(&1);
+    assert!(client.is_metadata_frozen(&1));
+    assert!(!client.is_metadata_frozen(&2));
+    client.set_base_uri(&String::from_str(&env, "ipfs://v2/"));
+    assert_eq!(client.token_uri(&1), String::from_str(&env, "ipfs://v1/1"));
+    assert_eq!(client.token_uri(&2), String::from_str(&env, "ipfs://v2/2"));
+
+    // Burning drops the pinned uri
+    #[cfg(feature = "burnable")]
+    {
+        client.burn(&user1, &1);
+        client.mint(&user1, &1);
+        assert!(!client.is_metadata_frozen(&1));
+        assert_eq!(client.token_uri(&1), String::from_str(&env, "ipfs://v2/1"));
+    }
+
+    client.freeze_all_metadata();
+    assert!(client.is_metadata_frozen(&2));
+    assert!(client.is_metadata_frozen(&3));
+}
+
+#[test]
+#[cfg(feature = "onchain-metadata")]
+fn onchain_metadata_test() {
+    let env = Env::default();
+    let client = setup(&env);
+
+    let user1 = Address::generate(&env);
+    client.mock_all_auths().mint(&user1, &1);
+    client.set_token_metadata(
+        &1,
+        &TokenMetadata {
+            name: String::from_str(&env, "Parcel \"1\""),
+            description: String::from_str(&env, "A wall parcel"),
+            image: String::from_str(&env, "ipfs://img/1.png"),
+            attributes: soroban_sdk::vec![
+                &env,
+                Attribute {
+                    trait_type: String::from_str(&env, "district"),
+                    value: String::from_str(&env, "north"),
+                },
+            ],
+        },
+    );
+
+    // {"name":"Parcel \"1\"","description":"A wall parcel","image":"ipfs://img/1.png",
+    //  "attributes":[{"trait_type":"district","value":"north"}]}
+    assert_eq!(
+        client.token_uri(&1),
+        String::from_str(
+            &env,
+            "data:application/json;base64,eyJuYW1lIjoiUGFyY2VsIFwiMVwiIiwiZGVzY3JpcHRpb24iOiJBIHdhbGwgcGFyY2VsIiwiaW1hZ2UiOiJpcGZzOi8vaW1nLzEucG5nIiwiYXR0cmlidXRlcyI6W3sidHJhaXRfdHlwZSI6ImRpc3RyaWN0IiwidmFsdWUiOiJub3J0aCJ9XX0="
+        )
+    );
+
+    let mut json = json::JsonBuilder::new();
+    json.begin_object()
+        .key("x")
+        .u32(12)
+        .key("y")
+        .i128(-3)
+        .key("tags")
+        .begin_array()
+        .str("a\nb")
+        .str("c")
+        .end_array()
+        .end_object();
+    assert_eq!(
+        json.as_bytes(),
+        b"{\"x\":12,\"y\":-3,\"tags\":[\"a\\nb\",\"c\"]}"
+    );
+}
+
+#[test]
+#[cfg(all(
+    feature = "enumerable",
+    feature = "burnable",
+    not(feature = "soulbound")
+))]
+fn enumerable_swap_test() {
+    let env = Env::default();
+    let client = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    for token_id in 1..=4 {
+        client.mock_all_auths().mint(&user1, &token_id);
+    }
+
+    // Removing the first token moves the last one into its slot
+    client
+        .mock_all_auths()
+        .transfer_from(&user1, &user1, &user2, &1);
+    assert_eq!(client.token_of_owner_by_index(&user1, &0), 4);
+    assert_eq!(client.token_of_owner_by_index(&user1, &1), 2);
+    assert_eq!(client.token_of_owner_by_index(&user1, &2), 3);
+
+    // The moved token can still be removed, its position was updated
+    client
+        .mock_all_auths()
+        .transfer_from(&user1, &user1, &user2, &4);
+    assert_eq!(client.token_of_owner_by_index(&user1, &0), 3);
+    assert_eq!(client.token_of_owner_by_index(&user1, &1), 2);
+    assert_eq!(client.token_of_owner_by_index(&user2, &1), 4);
+
+    client.mock_all_auths().burn(&user2, &1);
+    assert_eq!(client.total_supply(), 3);
+    assert_eq!(client.token_by_index(&0), 4);
+    assert_eq!(client.balance_of(&user2), 1);
+
+    env.as_contract(&client.address, || {
+        assert!(check_enumerable_invariants(&env));
+    });
+}
+
+#[test]
+#[cfg(feature = "enumerable")]
+fn pagination_test() {
+    let env = Env::default();
+    let client = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    env.budget().reset_unlimited();
+    for token_id in 0..150 {
+        client.mock_all_auths().mint(&user1, &token_id);
+    }
+    client.mock_all_auths().mint(&user2, &200);
+    env.budget().reset_default();
+
+    // Pages are capped
+    let page = client.all_tokens(&0, &1000);
+    assert_eq!(page.len(), 100);
+    assert_eq!(page.get(99), Some(99));
+    let page = client.all_tokens(&100, &100);
+    assert_eq!(page.len(), 51);
+    assert_eq!(page.get(50), Some(200));
+    assert_eq!(client.all_tokens(&151, &10).len(), 0);
+
+    assert_eq!(
+        client.tokens_of_owner(&user1, &148, &10),
+        vec![&env, 148, 149]
+    );
+    assert_eq!(client.tokens_of_owner(&user2, &0, &10), vec![&env, 200]);
+
+    assert_eq!(
+        client.owners_of(&vec![&env, 0, 200, 300]),
+        vec![&env, Some(user1), Some(user2), None]
+    );
+}
+
+#[test]
+#[cfg(not(feature = "soulbound"))]
+fn approval_test() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    client.mock_all_auths().mint(&owner, &1);
+    client.mock_all_auths().mint(&owner, &2);
+
+    client
+        .mock_all_auths()
+        .approve(&owner, &Some(spender.clone()), &1, &100);
+    assert_eq!(client.get_approved(&1), Some(spender.clone()));
+    assert_eq!(client.approval_expiration(&1), Some(100));
+
+    // Approvals lapse at their expiration ledger
+    env.ledger().with_mut(|l| l.sequence_number = 101);
+    assert_eq!(client.get_approved(&1), None);
+    assert_eq!(client.approval_expiration(&1), None);
+
+    // and are cleared by any transfer, even one made by the owner
+    client
+        .mock_all_auths()
+        .approve(&owner, &Some(spender.clone()), &1, &200);
+    client
+        .mock_all_auths()
+        .transfer_from(&owner, &owner, &buyer, &1);
+    assert_eq!(client.get_approved(&1), None);
+
+    client
+        .mock_all_auths()
+        .set_approval_for_all(&owner, &owner, &operator, &true, &NON_EXPIRING);
+    assert_eq!(
+        client.operator_expiration(&owner, &operator),
+        Some(NON_EXPIRING)
+    );
+    env.ledger().with_mut(|l| l.sequence_number = 3_000);
+    assert!(client.is_approval_for_all(&owner, &operator));
+
+    client.mock_all_auths().revoke_all_operators(&owner);
+    assert!(!client.is_approval_for_all(&owner, &operator));
+    assert_eq!(client.operator_expiration(&owner, &operator), None);
+
+    // New grants are not affected by the previous revocation
+    client
+        .mock_all_auths()
+        .set_approval_for_all(&owner, &owner, &operator, &true, &3_100);
+    client
+        .mock_all_auths()
+        .transfer_from(&operator, &owner, &buyer, &2);
+    assert_eq!(client.balance_of(&buyer), 2);
+}
+
+#[test]
+#[cfg(not(feature = "soulbound"))]
+fn conformance_test() {
+    let env = Env::default();
+    let client = setup(&env);
+
+    let next_id = core::cell::Cell::new(0);
+    let mint = |to: &Address| {
+        let token_id = next_id.get();
+        next_id.set(token_id + 1);
+        client.mock_all_auths().mint(to, &token_id);
+        token_id
+    };
+    let suite = erc721_testkit::Suite::new(&env, &client.address, &mint);
+    #[cfg(feature = "burnable")]
+    let suite = suite.burnable();
+    #[cfg(feature = "enumerable")]
+    let suite = suite.enumerable();
+    suite.run();
+}
+
+#[test]
+#[cfg(all(feature = "sep50", feature = "burnable", not(feature = "soulbound")))]
+fn sep50_test() {
+    use soroban_sdk::testutils::Events;
+    use soroban_sdk::{symbol_short, vec, IntoVal, Symbol};
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, SEP50Contract);
+    let client = sep50::SEP50ContractClient::new(&env, &contract_id);
+    let last_event = || vec![&env, env.events().all().last().unwrap()];
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let operator = Address::generate(&env);
+    client.mint(&user1, &1);
+    client.mint(&user1, &2);
+    assert_eq!(
+        last_event(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("mint"), user1.clone()).into_val(&env),
+                2u32.into_val(&env)
+            )
+        ]
+    );
+    assert_eq!(client.balance(&user1), 2);
+
+    client.transfer(&user1, &user2, &1);
+    assert_eq!(client.owner_of(&1), user2);
+    assert_eq!(
+        last_event(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("transfer"), user1.clone(), user2.clone()).into_val(&env),
+                1u32.into_val(&env)
+            )
+        ]
+    );
+
+    // Both interfaces read the same entries
+    env.as_contract(&contract_id, || {
+        assert_eq!(ERC721Contract::owner_of(env.clone(), 1), user2);
+        assert_eq!(ERC721Contract::balance_of(env.clone(), user1.clone()), 1);
+    });
+
+    client.approve(&user2, &operator, &1, &1000);
+    assert_eq!(client.get_approved(&1), Some(operator.clone()));
+    assert_eq!(
+        last_event(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("approve"), user2.clone(), 1u32).into_val(&env),
+                (operator.clone(), 1000u32).into_val(&env)
+            )
+        ]
+    );
+    // A zero ledger revokes the approval
+    client.approve(&user2, &operator, &1, &0);
+    assert_eq!(client.get_approved(&1), None);
+
+    client.approve_for_all(&user1, &operator, &1000);
+    assert!(client.is_approved_for_all(&user1, &operator));
+    assert_eq!(
+        last_event(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "approve_for_all"), user1.clone()).into_val(&env),
+                (operator.clone(), 1000u32).into_val(&env)
+            )
+        ]
+    );
+    client.transfer_from(&operator, &user1, &user2, &2);
+    assert_eq!(client.balance(&user2), 2);
+
+    client.approve(&user2, &operator, &2, &1000);
+    client.burn_from(&operator, &user2, &2);
+    client.burn(&user2, &1);
+    assert_eq!(client.balance(&user2), 0);
+    assert_eq!(
+        last_event(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("burn"), user2.clone()).into_val(&env),
+                1u32.into_val(&env)
+            )
+        ]
+    );
+
+    client.approve_for_all(&user1, &operator, &0);
+    assert!(!client.is_approved_for_all(&user1, &operator));
+}
+
+#[test]
+fn introspection_test() {
+    use soroban_sdk::Symbol;
+
+    let env = Env::default();
+    let client = setup(&env);
+    let interface = |name| Symbol::new(&env, name);
+
+    assert!(client.supports_interface(&interface(introspection::ERC721)));
+    assert!(client.supports_interface(&interface(introspection::INTROSPECTION)));
+    assert_eq!(
+        client.supports_interface(&interface(introspection::ENUMERABLE)),
+        cfg!(feature = "enumerable")
+    );
+    assert_eq!(
+        client.supports_interface(&interface(introspection::BURNABLE)),
+        cfg!(feature = "burnable")
+    );
+    assert_eq!(
+        client.supports_interface(&interface(introspection::LOCKABLE)),
+        cfg!(feature = "lockable")
+    );
+    assert!(!client.supports_interface(&interface(introspection::CONSECUTIVE)));
+    assert!(!client.supports_interface(&interface("erc1155")));
+
+    // Interfaces implemented by the wrapping contract
+    assert!(!client.supports_interface(&interface(introspection::ROYALTY)));
+    client.register_interface(&interface(introspection::ROYALTY));
+    client.register_interface(&interface(introspection::ROYALTY));
+    assert!(client.supports_interface(&interface(introspection::ROYALTY)));
+}
+
+#[test]
+#[cfg(all(feature = "nested", not(feature = "soulbound")))]
+fn nested_test() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let other = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    client.mint(&user1, &1);
+    client.mint(&user1, &2);
+    client.mint(&user1, &3);
+    let estate = ParentToken {
+        contract: client.address.clone(),
+        token_id: 1,
+    };
+
+    // Parcels 2 and 3 go in the estate, 3 one level deeper
+    client.transfer_to_parent(&user1, &user1, &2, &estate);
+    let parcel = ParentToken {
+        contract: client.address.clone(),
+        token_id: 2,
+    };
+    client.transfer_to_parent(&user1, &user1, &3, &parcel);
+    assert_eq!(client.owner_of(&3), client.address);
+    assert_eq!(client.parent_of(&3), Some(parcel));
+    assert_eq!(client.root_owner_of(&3), user1);
+    assert_eq!(client.balance_of(&user1), 1);
+
+    // The tree follows its root
+    client.transfer_from(&user1, &user1, &user2, &1);
+    assert_eq!(client.root_owner_of(&3), user2);
+    client.transfer_from_parent(&user2, &3, &user2);
+    assert_eq!(client.owner_of(&3), user2);
+    assert_eq!(client.parent_of(&3), None);
+    assert!(client.children_of(&client.address, &2).is_empty());
+    assert_eq!(
+        client.children_of(&client.address, &1),
+        soroban_sdk::vec![&env, 2]
+    );
+
+    // Parents can live in another collection
+    other.mint(&user1, &7);
+    let foreign = ParentToken {
+        contract: other.address.clone(),
+        token_id: 7,
+    };
+    client.transfer_to_parent(&user2, &user2, &3, &foreign);
+    assert_eq!(client.root_owner_of(&3), user1);
+    other.transfer_from(&user1, &user1, &user2, &7);
+    assert_eq!(client.root_owner_of(&3), user2);
+}
+
+#[cfg(feature = "operator-filter")]
+mod registry {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    /// Allows every operator but the one stored
+    #[contract]
+    pub struct Registry;
+
+    #[contractimpl]
+    impl Registry {
+        pub fn block(env: Env, operator: Address) {
+            env.storage().instance().set(&0u32, &operator);
+        }
+        pub fn is_operator_allowed(env: Env, collection: Address, operator: Address) -> bool {
+            let _ = collection;
+            env.storage().instance().get(&0u32) != Some(operator)
+        }
+    }
+}
+
+#[test]
+#[cfg(all(feature = "operator-filter", not(feature = "soulbound")))]
+fn operator_filter_test() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let market = Address::generate(&env);
+    let other = Address::generate(&env);
+    client.mint(&user1, &1);
+    assert_eq!(client.operator_filter(), OperatorFilter::Open);
+    assert!(client.is_operator_allowed(&other));
+
+    client.set_operator_filter(&OperatorFilter::Allowlist);
+    client.allow_operator(&market, &true);
+    assert!(client.is_operator_allowed(&market));
+    assert!(!client.is_operator_allowed(&other));
+    client.set_approval_for_all(&user1, &user1, &market, &true, &NON_EXPIRING);
+    client.transfer_from(&market, &user1, &user2, &1);
+    assert_eq!(client.owner_of(&1), user2);
+
+    // The lists are kept apart
+    client.set_operator_filter(&OperatorFilter::Denylist);
+    client.deny_operator(&market, &true);
+    assert!(!client.is_operator_allowed(&market));
+    assert!(client.is_operator_allowed(&other));
+
+    let registry_id = env.register_contract(None, registry::Registry);
+    registry::RegistryClient::new(&env, &registry_id).block(&other);
+    client.set_operator_filter(&OperatorFilter::Registry(registry_id));
+    assert!(client.is_operator_allowed(&market));
+    assert!(!client.is_operator_allowed(&other));
+    client.approve(&user2, &Some(market), &1, &NON_EXPIRING);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+#[cfg(all(feature = "operator-filter", not(feature = "soulbound")))]
+fn operator_filter_denied_test() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let market = Address::generate(&env);
+    client.mint(&user1, &1);
+    client.set_approval_for_all(&user1, &user1, &market, &true, &NON_EXPIRING);
+
+    // Grants made before the operator was denied don't let it through
+    client.set_operator_filter(&OperatorFilter::Denylist);
+    client.deny_operator(&market, &true);
+    reject(&client, || {
+        ERC721Contract::transfer_from(env.clone(), market, user1, user2, 1)
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+#[cfg(feature = "operator-filter")]
+fn operator_filter_unlisted_test() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let market = Address::generate(&env);
+    client.mint(&user1, &1);
+
+    client.set_operator_filter(&OperatorFilter::Allowlist);
+    reject(&client, || {
+        ERC721Contract::approve(env.clone(), user1, Some(market), 1, NON_EXPIRING)
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+#[cfg(all(
+    feature = "operator-filter",
+    feature = "nested",
+    not(feature = "soulbound")
+))]
+fn operator_filter_nested_test() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let market = Address::generate(&env);
+    client.mint(&user1, &1);
+    client.mint(&user1, &2);
+    let estate = ParentToken {
+        contract: client.address.clone(),
+        token_id: 1,
+    };
+    client.transfer_to_parent(&user1, &user1, &2, &estate);
+    client.set_approval_for_all(&user1, &user1, &market, &true, &NON_EXPIRING);
+
+    // Nested tokens don't bypass the filter
+    client.set_operator_filter(&OperatorFilter::Denylist);
+    client.deny_operator(&market, &true);
+    reject(&client, || {
+        ERC721Contract::transfer_from_parent(env.clone(), market, 2, user2)
+    });
+}
+
+#[test]
+#[cfg(feature = "regulated")]
+fn regulated_test() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let regulator = Address::generate(&env);
+    client.mint(&user1, &1);
+    assert!(!client.clawback_enabled());
+
+    client.set_regulator(&Some(regulator.clone()));
+    assert!(client.clawback_enabled());
+    assert_eq!(client.regulator(), Some(regulator));
+
+    client.freeze_token(&1, &true);
+    client.freeze_account(&user1, &true);
+    assert!(client.is_token_frozen(&1));
+    assert!(client.is_account_frozen(&user1));
+
+    // Clawback ignores the freezes
+    client.clawback(&1, &user2);
+    assert_eq!(client.owner_of(&1), user2);
+    assert_eq!(client.balance_of(&user1), 0);
+
+    client.freeze_token(&1, &false);
+    assert!(!client.is_token_frozen(&1));
+    client.set_regulator(&None);
+    assert!(!client.clawback_enabled());
+    assert!(client.is_account_frozen(&user1));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+#[cfg(all(feature = "regulated", not(feature = "soulbound")))]
+fn frozen_token_test() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    client.mint(&user1, &1);
+    client.set_regulator(&Some(Address::generate(&env)));
+
+    client.freeze_token(&1, &true);
+    reject(&client, || {
+        ERC721Contract::transfer_from(env.clone(), user1.clone(), user1, user2, 1)
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+#[cfg(all(feature = "regulated", not(feature = "soulbound")))]
+fn frozen_account_test() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    client.mint(&user1, &1);
+    client.set_regulator(&Some(Address::generate(&env)));
+
+    // Receiving is frozen too
+    client.freeze_account(&user2, &true);
+    reject(&client, || {
+        ERC721Contract::transfer_from(env.clone(), user1.clone(), user1, user2, 1)
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+#[cfg(all(feature = "regulated", feature = "nested", not(feature = "soulbound")))]
+fn frozen_nested_test() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    client.mint(&user1, &1);
+    client.mint(&user1, &2);
+    let estate = ParentToken {
+        contract: client.address.clone(),
+        token_id: 1,
+    };
+    client.transfer_to_parent(&user1, &user1, &2, &estate);
+    client.set_regulator(&Some(Address::generate(&env)));
+
+    // A frozen root owner can't pull the children out either
+    client.freeze_account(&user1, &true);
+    reject(&client, || {
+        ERC721Contract::transfer_from_parent(env.clone(), user1, 2, user2)
+    });
+}
+
+#[test]
+#[cfg(feature = "attributes")]
+fn attributes_test() {
+    use soroban_sdk::{map, String, Symbol};
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let curator = Address::generate(&env);
+    client.mint(&user1, &1);
+    client.mint(&user1, &2);
+    client.set_metadata_role(&Some(curator.clone()));
+    assert_eq!(client.metadata_role(), curator);
+
+    let district = Symbol::new(&env, "district");
+    let edition = Symbol::new(&env, "edition");
+    let north = AttributeValue::String(String::from_str(&env, "north"));
+    client.set_attribute(&1, &district, &Some(north.clone()));
+    client.set_attribute(&1, &edition, &Some(AttributeValue::U32(3)));
+    client.set_attribute(&2, &edition, &Some(AttributeValue::I128(-1)));
+    client.set_collection_attribute(&edition, &Some(AttributeValue::U32(1)));
+    assert_eq!(client.attribute(&1, &district), Some(north.clone()));
+    assert_eq!(
+        client.attributes_of(&soroban_sdk::vec![&env, 1, 2, 3]),
+        soroban_sdk::vec![
+            &env,
+            map![
+                &env,
+                (district.clone(), north.clone()),
+                (edition.clone(), AttributeValue::U32(3))
+            ],
+            map![&env, (edition.clone(), AttributeValue::I128(-1))],
+            map![&env],
+        ]
+    );
+    assert_eq!(
+        client.collection_attributes(),
+        map![&env, (edition.clone(), AttributeValue::U32(1))]
+    );
+
+    client.set_attribute(&2, &edition, &None);
+    assert!(client.attributes(&2).is_empty());
+
+    // Stored attributes come after the ones of the on-chain metadata
+    #[cfg(feature = "onchain-metadata")]
+    {
+        let text = |s| String::from_str(&env, s);
+        client.set_token_metadata(
+            &1,
+            &TokenMetadata {
+                name: text("Parcel 1"),
+                description: text(""),
+                image: text("ipfs://img/1.png"),
+                attributes: soroban_sdk::vec![&env],
+            },
+        );
+        let mut json = json::JsonBuilder::new();
+        json.begin_object()
+            .key("name")
+            .str("Parcel 1")
+            .key("description")
+            .str("")
+            .key("image")
+            .str("ipfs://img/1.png")
+            .key("attributes")
+            .begin_array();
+        json.begin_object()
+            .key("trait_type")
+            .str("district")
+            .key("value")
+            .str("north")
+            .end_object();
+        json.begin_object()
+            .key("trait_type")
+            .str("edition")
+            .key("value")
+            .u32(3)
+            .end_object();
+        json.end_array().end_object();
+        assert_eq!(client.token_uri(&1), json.to_data_uri(&env));
+    }
+}
+
+#[test]
+#[cfg(feature = "vouchers")]
+fn voucher_test() {
+    use ed25519_dalek::{Signer, SigningKey};
+    use soroban_sdk::{token, BytesN};
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let admin = env.as_contract(&client.address, || get_admin(&env));
+
+    let key = SigningKey::from_bytes(&[7; 32]);
+    client.set_voucher_signer(&BytesN::from_array(&env, &key.verifying_key().to_bytes()));
+    let sign = |voucher: &MintVoucher| {
+        let message = env.as_contract(&client.address, || voucher::message(&env, voucher));
+        let message: std::vec::Vec<u8> = message.iter().collect();
+        BytesN::from_array(&env, &key.sign(&message).to_bytes())
+    };
+
+    let asset = env.register_stellar_asset_contract(Address::generate(&env));
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &asset).mint(&buyer, &100);
+
+    // An open voucher for the next free id
+    client.mint(&buyer, &4);
+    let voucher = MintVoucher {
+        recipient: Recipient::Anyone,
+        token_id: None,
+        uri: String::from_str(&env, "ipfs://lazy/5"),
+        price: 30,
+        asset: asset.clone(),
+        expiration_ledger: 100,
+        nonce: 1,
+    };
+    assert_eq!(client.redeem_voucher(&buyer, &voucher, &sign(&voucher)), 5);
+    assert_eq!(client.owner_of(&5), buyer);
+    assert_eq!(
+        client.token_uri(&5),
+        String::from_str(&env, "ipfs://lazy/5")
+    );
+    assert_eq!(token::Client::new(&env, &asset).balance(&admin), 30);
+
+    let voucher = MintVoucher {
+        recipient: Recipient::Account(buyer.clone()),
+        token_id: Some(1),
+        uri: String::from_str(&env, ""),
+        price: 0,
+        asset,
+        expiration_ledger: 100,
+        nonce: 2,
+    };
+    assert_eq!(client.redeem_voucher(&buyer, &voucher, &sign(&voucher)), 1);
+    assert_eq!(client.balance_of(&buyer), 3);
+}
+
+/// Register `key` as the voucher signer and return a free voucher for the next
+/// id, open to anyone until ledger 100
+#[cfg(feature = "vouchers")]
+fn voucher_setup(env: &Env, client: &ERC721ContractClient, key: &[u8; 32]) -> MintVoucher {
+    use ed25519_dalek::SigningKey;
+
+    let key = SigningKey::from_bytes(key);
+    client.set_voucher_signer(&soroban_sdk::BytesN::from_array(
+        env,
+        &key.verifying_key().to_bytes(),
+    ));
+    MintVoucher {
+        recipient: Recipient::Anyone,
+        token_id: None,
+        uri: String::from_str(env, ""),
+        price: 0,
+        asset: Address::generate(env),
+        expiration_ledger: 100,
+        nonce: 1,
+    }
+}
+
+#[cfg(feature = "vouchers")]
+fn sign_voucher(
+    client: &ERC721ContractClient,
+    key: &[u8; 32],
+    voucher: &MintVoucher,
+) -> soroban_sdk::BytesN<64> {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    let env = &client.env;
+    let message = env.as_contract(&client.address, || voucher::message(env, voucher));
+    let message: std::vec::Vec<u8> = message.iter().collect();
+    let signature = SigningKey::from_bytes(key).sign(&message);
+    soroban_sdk::BytesN::from_array(env, &signature.to_bytes())
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #20)")]
+#[cfg(feature = "vouchers")]
+fn voucher_expired_test() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let voucher = voucher_setup(&env, &client, &[7; 32]);
+    let signature = sign_voucher(&client, &[7; 32], &voucher);
+    env.ledger().with_mut(|l| l.sequence_number = 101);
+    let buyer = Address::generate(&env);
+    reject(&client, || {
+        ERC721Contract::redeem_voucher(env.clone(), buyer, voucher, signature);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #21)")]
+#[cfg(feature = "vouchers")]
+fn voucher_replayed_test() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let voucher = voucher_setup(&env, &client, &[7; 32]);
+    let signature = sign_voucher(&client, &[7; 32], &voucher);
+    let buyer = Address::generate(&env);
+    client.redeem_voucher(&buyer, &voucher, &signature);
+    reject(&client, || {
+        ERC721Contract::redeem_voucher(env.clone(), buyer, voucher, signature);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Crypto, InvalidInput)")]
+#[cfg(feature = "vouchers")]
+fn voucher_wrong_signer_test() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let voucher = voucher_setup(&env, &client, &[7; 32]);
+    let signature = sign_voucher(&client, &[8; 32], &voucher);
+    let buyer = Address::generate(&env);
+    reject(&client, || {
+        ERC721Contract::redeem_voucher(env.clone(), buyer, voucher, signature);
+    });
+}
+
+#[cfg(feature = "delegation")]
+mod delegations {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    /// Delegates a single (delegate, owner) pair for everything
+    #[contract]
+    pub struct Registry;
+
+    #[contractimpl]
+    impl Registry {
+        pub fn delegate(env: Env, owner: Address, delegate: Address) {
+            env.storage().instance().set(&(delegate, owner), &true);
+        }
+        pub fn check_delegate(
+            env: Env,
+            delegate: Address,
+            owner: Address,
+            contract: Address,
+            token_id: u32,
+        ) -> bool {
+            let _ = (contract, token_id);
+            env.storage().instance().has(&(delegate, owner))
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "delegation")]
+fn delegation_test() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let cold = Address::generate(&env);
+    let hot = Address::generate(&env);
+    client.mint(&cold, &1);
+    assert!(client.is_owner_or_delegate(&cold, &1));
+    assert!(!client.is_owner_or_delegate(&hot, &1));
+
+    let registry_id = env.register_contract(None, delegations::Registry);
+    delegations::RegistryClient::new(&env, &registry_id).delegate(&cold, &hot);
+    client.set_delegation_registry(&registry_id);
+    assert!(client.is_owner_or_delegate(&hot, &1));
+    env.as_contract(&client.address, || require_owner_or_delegate(&env, &hot, 1));
+}
+
+#[test]
+#[cfg(all(feature = "provenance", not(feature = "soulbound")))]
+fn provenance_test() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    client.set_provenance_length(&3);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let user3 = Address::generate(&env);
+    let at = |ledger| env.ledger().with_mut(|l| l.sequence_number = ledger);
+    at(1);
+    client.mint(&user1, &1);
+    at(2);
+    client.transfer_from(&user1, &user1, &user2, &1);
+    at(3);
+    client.transfer_from(&user2, &user2, &user3, &1);
+    at(4);
+    client.transfer_from(&user3, &user3, &user1, &1);
+    at(5);
+    client.transfer_from(&user1, &user1, &user2, &1);
+
+    // Only the last three changes are kept
+    let entry = |from: &Address, to: &Address, ledger| ProvenanceEntry {
+        from: from.clone(),
+        to: to.clone(),
+        ledger,
+    };
+    assert_eq!(
+        client.provenance(&1, &0, &10),
+        soroban_sdk::vec![
+            &env,
+            entry(&user2, &user3, 3),
+            entry(&user3, &user1, 4),
+            entry(&user1, &user2, 5),
+        ]
+    );
+    assert_eq!(
+        client.provenance(&1, &2, &10),
+        soroban_sdk::vec![&env, entry(&user1, &user2, 5)]
+    );
+
+    // A longer buffer keeps the history recorded so far
+    client.set_provenance_length(&5);
+    at(6);
+    client.transfer_from(&user2, &user2, &user3, &1);
+    assert_eq!(client.provenance(&1, &0, &10).len(), 4);
+    assert_eq!(
+        client.provenance(&1, &0, &1),
+        soroban_sdk::vec![&env, entry(&user2, &user3, 3)]
+    );
+
+    // A consecutive batch writes no log, the mint is read from the batch
+    at(7);
+    let first = client.mint_consecutive(&user1, &2);
+    let contract = client.address.clone();
+    assert_eq!(
+        client.provenance(&(first + 1), &0, &10),
+        soroban_sdk::vec![&env, entry(&contract, &user1, 7)]
+    );
+    at(8);
+    client.transfer_from(&user1, &user1, &user2, &first);
+    assert_eq!(
+        client.provenance(&first, &0, &10),
+        soroban_sdk::vec![&env, entry(&contract, &user1, 7), entry(&user1, &user2, 8)]
+    );
+}
@@ -1,4 +1,6 @@
-use soroban_sdk::{contracterror, contracttype, Address, Env, IntoVal, TryFromVal, Val};
+use soroban_sdk::{
+    contracterror, contracttype, Address, BytesN, Env, IntoVal, String, TryFromVal, Val, Vec,
+};
 
 use storage::*;
 
@@ -28,66 +30,341 @@ impl storage::Storage for Admin {
         storage::Persistent::remove(env, self)
     }
 }
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Approval {
+    pub address: Address,
+    pub expiration_ledger: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperatorGrant {
+    pub epoch: u32,
+    pub expiration_ledger: u32,
+}
+
 #[contracttype]
 pub enum DataKey {
-    Balance(Address),           // instance
-    TokenOwner(u32),            // instance
-    Approved(u32),              // temporary
-    Operator(Address, Address), // temporary
+    Balance(Address),           // persistent
+    TokenOwner(u32),            // persistent
+    Approved(u32),              // persistent
+    Operator(Address, Address), // persistent
+    OperatorEpoch(Address),     // persistent
 }
 impl Storage for DataKey {
+    fn get<V: TryFromVal<Env, Val>>(&self, env: &Env) -> Option<V> {
+        storage::Persistent::get(env, self)
+    }
+
+    fn set<V: IntoVal<Env, Val>>(&self, env: &Env, val: &V) {
+        storage::Persistent::set(env, self, val)
+    }
+
+    fn has(&self, env: &Env) -> bool {
+        storage::Persistent::has(env, self)
+    }
+
+    fn extend(&self, env: &Env, min_ledger_to_live: u32) -> &Self {
+        storage::Persistent::extend(env, self, min_ledger_to_live);
+        self
+    }
+
+    fn remove(&self, env: &Env) {
+        storage::Persistent::remove(env, self)
+    }
+}
+
+#[contracttype]
+pub enum DatakeyMetadata {
+    Name,         // instance
+    Symbol,       // instance
+    BaseUri,      // instance
+    ContractUri,  // instance
+    FrozenAll,    // instance
+    Uri(u32),     // persistent
+    Onchain(u32), // persistent
+    Frozen(u32),  // persistent
+}
+impl storage::Storage for DatakeyMetadata {
     fn get<V: TryFromVal<Env, Val>>(&self, env: &Env) -> Option<V> {
         match self {
-            DataKey::Balance(_) | DataKey::TokenOwner(_) => storage::Persistent::get(env, self),
-            DataKey::Approved(_) | DataKey::Operator(_, _) => storage::Temporary::get(env, self),
+            DatakeyMetadata::Uri(_) | DatakeyMetadata::Onchain(_) | DatakeyMetadata::Frozen(_) => {
+                storage::Persistent::get(env, self)
+            }
+            _ => storage::Instance::get(env, self),
         }
     }
 
     fn set<V: IntoVal<Env, Val>>(&self, env: &Env, val: &V) {
         match self {
-            DataKey::Balance(_) | DataKey::TokenOwner(_) => {
+            DatakeyMetadata::Uri(_) | DatakeyMetadata::Onchain(_) | DatakeyMetadata::Frozen(_) => {
                 storage::Persistent::set(env, self, val)
             }
-            DataKey::Approved(_) | DataKey::Operator(_, _) => {
-                storage::Temporary::set(env, self, val)
-            }
+            _ => storage::Instance::set(env, self, val),
         }
     }
 
     fn has(&self, env: &Env) -> bool {
         match self {
-            DataKey::Balance(_) | DataKey::TokenOwner(_) => storage::Persistent::has(env, self),
-            DataKey::Approved(_) | DataKey::Operator(_, _) => storage::Temporary::has(env, self),
+            DatakeyMetadata::Uri(_) | DatakeyMetadata::Onchain(_) | DatakeyMetadata::Frozen(_) => {
+                storage::Persistent::has(env, self)
+            }
+            _ => storage::Instance::has(env, self),
         }
     }
 
     fn extend(&self, env: &Env, min_ledger_to_live: u32) -> &Self {
         match self {
-            DataKey::Balance(_) | DataKey::TokenOwner(_) => {
+            DatakeyMetadata::Uri(_) | DatakeyMetadata::Onchain(_) | DatakeyMetadata::Frozen(_) => {
                 storage::Persistent::extend(env, self, min_ledger_to_live)
             }
-            DataKey::Approved(_) | DataKey::Operator(_, _) => {
-                storage::Temporary::extend(env, self, min_ledger_to_live)
+            _ => storage::Instance::extend(env, min_ledger_to_live),
+        };
+        self
+    }
+
+    fn remove(&self, env: &Env) {
+        match self {
+            DatakeyMetadata::Uri(_) | DatakeyMetadata::Onchain(_) | DatakeyMetadata::Frozen(_) => {
+                storage::Persistent::remove(env, self)
             }
+            _ => storage::Instance::remove(env, self),
+        }
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attribute {
+    pub trait_type: String,
+    pub value: String,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenMetadata {
+    pub name: String,
+    pub description: String,
+    pub image: String,
+    pub attributes: Vec<Attribute>,
+}
+
+#[contracttype]
+pub enum DataKeyEnumerable {
+    TotalSupply,                   // instance
+    IndexToken(u32),               // persistent
+    TokenIndex(u32),               // persistent
+    OwnerIndexToken(Address, u32), // persistent
+    OwnerTokenIndex(u32),          // persistent
+}
+impl storage::Storage for DataKeyEnumerable {
+    fn get<V: TryFromVal<Env, Val>>(&self, env: &Env) -> Option<V> {
+        match self {
+            DataKeyEnumerable::TotalSupply => storage::Instance::get(env, self),
+            _ => storage::Persistent::get(env, self),
+        }
+    }
+
+    fn set<V: IntoVal<Env, Val>>(&self, env: &Env, val: &V) {
+        match self {
+            DataKeyEnumerable::TotalSupply => storage::Instance::set(env, self, val),
+            _ => storage::Persistent::set(env, self, val),
+        }
+    }
+
+    fn has(&self, env: &Env) -> bool {
+        match self {
+            DataKeyEnumerable::TotalSupply => storage::Instance::has(env, self),
+            _ => storage::Persistent::has(env, self),
+        }
+    }
+
+    fn extend(&self, env: &Env, min_ledger_to_live: u32) -> &Self {
+        match self {
+            DataKeyEnumerable::TotalSupply => storage::Instance::extend(env, min_ledger_to_live),
+            _ => storage::Persistent::extend(env, self, min_ledger_to_live),
         };
         self
     }
 
     fn remove(&self, env: &Env) {
         match self {
-            DataKey::Balance(_) | DataKey::TokenOwner(_) => storage::Persistent::remove(env, self),
-            DataKey::Approved(_) | DataKey::Operator(_, _) => storage::Temporary::remove(env, self),
+            DataKeyEnumerable::TotalSupply => storage::Instance::remove(env, self),
+            _ => storage::Persistent::remove(env, self),
         }
     }
 }
 
 #[contracttype]
-pub enum DatakeyMetadata {
-    Name,     // instance
-    Symbol,   // instance
-    Uri(u32), // instance
+pub enum DataKeyConsecutive {
+    NextId,      // instance
+    Batches,     // instance
+    Batch(u32),  // persistent
+    Burned(u32), // persistent
 }
-impl storage::Storage for DatakeyMetadata {
+impl storage::Storage for DataKeyConsecutive {
+    fn get<V: TryFromVal<Env, Val>>(&self, env: &Env) -> Option<V> {
+        match self {
+            DataKeyConsecutive::NextId | DataKeyConsecutive::Batches => {
+                storage::Instance::get(env, self)
+            }
+            DataKeyConsecutive::Batch(_) | DataKeyConsecutive::Burned(_) => {
+                storage::Persistent::get(env, self)
+            }
+        }
+    }
+
+    fn set<V: IntoVal<Env, Val>>(&self, env: &Env, val: &V) {
+        match self {
+            DataKeyConsecutive::NextId | DataKeyConsecutive::Batches => {
+                storage::Instance::set(env, self, val)
+            }
+            DataKeyConsecutive::Batch(_) | DataKeyConsecutive::Burned(_) => {
+                storage::Persistent::set(env, self, val)
+            }
+        }
+    }
+
+    fn has(&self, env: &Env) -> bool {
+        match self {
+            DataKeyConsecutive::NextId | DataKeyConsecutive::Batches => {
+                storage::Instance::has(env, self)
+            }
+            DataKeyConsecutive::Batch(_) | DataKeyConsecutive::Burned(_) => {
+                storage::Persistent::has(env, self)
+            }
+        }
+    }
+
+    fn extend(&self, env: &Env, min_ledger_to_live: u32) -> &Self {
+        match self {
+            DataKeyConsecutive::NextId | DataKeyConsecutive::Batches => {
+                storage::Instance::extend(env, min_ledger_to_live)
+            }
+            DataKeyConsecutive::Batch(_) | DataKeyConsecutive::Burned(_) => {
+                storage::Persistent::extend(env, self, min_ledger_to_live)
+            }
+        };
+        self
+    }
+
+    fn remove(&self, env: &Env) {
+        match self {
+            DataKeyConsecutive::NextId | DataKeyConsecutive::Batches => {
+                storage::Instance::remove(env, self)
+            }
+            DataKeyConsecutive::Batch(_) | DataKeyConsecutive::Burned(_) => {
+                storage::Persistent::remove(env, self)
+            }
+        }
+    }
+}
+
+#[contracttype]
+pub enum DataKeyLock {
+    Locker(u32), // persistent
+}
+impl storage::Storage for DataKeyLock {
+    fn get<V: TryFromVal<Env, Val>>(&self, env: &Env) -> Option<V> {
+        storage::Persistent::get(env, self)
+    }
+
+    fn set<V: IntoVal<Env, Val>>(&self, env: &Env, val: &V) {
+        storage::Persistent::set(env, self, val)
+    }
+
+    fn has(&self, env: &Env) -> bool {
+        storage::Persistent::has(env, self)
+    }
+
+    fn extend(&self, env: &Env, min_ledger_to_live: u32) -> &Self {
+        storage::Persistent::extend(env, self, min_ledger_to_live);
+        self
+    }
+
+    fn remove(&self, env: &Env) {
+        storage::Persistent::remove(env, self)
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UserInfo {
+    pub user: Address,
+    pub expires_ledger: u32,
+}
+
+#[contracttype]
+pub enum DataKeyRentable {
+    User(u32), // persistent
+}
+impl storage::Storage for DataKeyRentable {
+    fn get<V: TryFromVal<Env, Val>>(&self, env: &Env) -> Option<V> {
+        storage::Persistent::get(env, self)
+    }
+
+    fn set<V: IntoVal<Env, Val>>(&self, env: &Env, val: &V) {
+        storage::Persistent::set(env, self, val)
+    }
+
+    fn has(&self, env: &Env) -> bool {
+        storage::Persistent::has(env, self)
+    }
+
+    fn extend(&self, env: &Env, min_ledger_to_live: u32) -> &Self {
+        storage::Persistent::extend(env, self, min_ledger_to_live);
+        self
+    }
+
+    fn remove(&self, env: &Env) {
+        storage::Persistent::remove(env, self)
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    pub ledger: u32,
+    pub votes: u32,
+}
+
+#[contracttype]
+pub enum DataKeyVotes {
+    Delegate(Address),        // persistent
+    CheckpointCount(Address), // persistent
+    Checkpoint(Address, u32), // persistent
+    TotalCheckpointCount,     // persistent
+    TotalCheckpoint(u32),     // persistent
+}
+impl storage::Storage for DataKeyVotes {
+    fn get<V: TryFromVal<Env, Val>>(&self, env: &Env) -> Option<V> {
+        storage::Persistent::get(env, self)
+    }
+
+    fn set<V: IntoVal<Env, Val>>(&self, env: &Env, val: &V) {
+        storage::Persistent::set(env, self, val)
+    }
+
+    fn has(&self, env: &Env) -> bool {
+        storage::Persistent::has(env, self)
+    }
+
+    fn extend(&self, env: &Env, min_ledger_to_live: u32) -> &Self {
+        storage::Persistent::extend(env, self, min_ledger_to_live);
+        self
+    }
+
+    fn remove(&self, env: &Env) {
+        storage::Persistent::remove(env, self)
+    }
+}
+
+#[contracttype]
+pub enum DataKeyIntrospection {
+    Interfaces, // instance
+}
+impl storage::Storage for DataKeyIntrospection {
     fn get<V: TryFromVal<Env, Val>>(&self, env: &Env) -> Option<V> {
         storage::Instance::get(env, self)
     }
@@ -111,13 +388,265 @@ impl storage::Storage for DatakeyMetadata {
 }
 
 #[contracttype]
-pub enum DataKeyEnumerable {
-    IndexToken,               // instance
-    TokenIndex,               // instance
-    OwnerIndexToken(Address), // instance
-    OwnerTokenIndex(Address), // instance
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParentToken {
+    pub contract: Address,
+    pub token_id: u32,
 }
-impl storage::Storage for DataKeyEnumerable {
+
+#[contracttype]
+pub enum DataKeyNested {
+    Parent(u32),            // persistent
+    Children(Address, u32), // persistent
+}
+impl storage::Storage for DataKeyNested {
+    fn get<V: TryFromVal<Env, Val>>(&self, env: &Env) -> Option<V> {
+        storage::Persistent::get(env, self)
+    }
+
+    fn set<V: IntoVal<Env, Val>>(&self, env: &Env, val: &V) {
+        storage::Persistent::set(env, self, val)
+    }
+
+    fn has(&self, env: &Env) -> bool {
+        storage::Persistent::has(env, self)
+    }
+
+    fn extend(&self, env: &Env, min_ledger_to_live: u32) -> &Self {
+        storage::Persistent::extend(env, self, min_ledger_to_live);
+        self
+    }
+
+    fn remove(&self, env: &Env) {
+        storage::Persistent::remove(env, self)
+    }
+}
+
+/// Which operators can be approved and act on the tokens of others
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OperatorFilter {
+    Open,
+    Allowlist,
+    Denylist,
+    Registry(Address),
+}
+
+#[contracttype]
+pub enum DataKeyOperatorFilter {
+    Filter,           // instance
+    Allowed(Address), // persistent
+    Denied(Address),  // persistent
+}
+impl storage::Storage for DataKeyOperatorFilter {
+    fn get<V: TryFromVal<Env, Val>>(&self, env: &Env) -> Option<V> {
+        match self {
+            DataKeyOperatorFilter::Filter => storage::Instance::get(env, self),
+            _ => storage::Persistent::get(env, self),
+        }
+    }
+
+    fn set<V: IntoVal<Env, Val>>(&self, env: &Env, val: &V) {
+        match self {
+            DataKeyOperatorFilter::Filter => storage::Instance::set(env, self, val),
+            _ => storage::Persistent::set(env, self, val),
+        }
+    }
+
+    fn has(&self, env: &Env) -> bool {
+        match self {
+            DataKeyOperatorFilter::Filter => storage::Instance::has(env, self),
+            _ => storage::Persistent::has(env, self),
+        }
+    }
+
+    fn extend(&self, env: &Env, min_ledger_to_live: u32) -> &Self {
+        match self {
+            DataKeyOperatorFilter::Filter => storage::Instance::extend(env, min_ledger_to_live),
+            _ => storage::Persistent::extend(env, self, min_ledger_to_live),
+        }
+        self
+    }
+
+    fn remove(&self, env: &Env) {
+        match self {
+            DataKeyOperatorFilter::Filter => storage::Instance::remove(env, self),
+            _ => storage::Persistent::remove(env, self),
+        }
+    }
+}
+
+#[contracttype]
+pub enum DataKeyRegulated {
+    Regulator,              // instance
+    FrozenToken(u32),       // persistent
+    FrozenAccount(Address), // persistent
+}
+impl storage::Storage for DataKeyRegulated {
+    fn get<V: TryFromVal<Env, Val>>(&self, env: &Env) -> Option<V> {
+        match self {
+            DataKeyRegulated::Regulator => storage::Instance::get(env, self),
+            _ => storage::Persistent::get(env, self),
+        }
+    }
+
+    fn set<V: IntoVal<Env, Val>>(&self, env: &Env, val: &V) {
+        match self {
+            DataKeyRegulated::Regulator => storage::Instance::set(env, self, val),
+            _ => storage::Persistent::set(env, self, val),
+        }
+    }
+
+    fn has(&self, env: &Env) -> bool {
+        match self {
+            DataKeyRegulated::Regulator => storage::Instance::has(env, self),
+            _ => storage::Persistent::has(env, self),
+        }
+    }
+
+    fn extend(&self, env: &Env, min_ledger_to_live: u32) -> &Self {
+        match self {
+            DataKeyRegulated::Regulator => storage::Instance::extend(env, min_ledger_to_live),
+            _ => storage::Persistent::extend(env, self, min_ledger_to_live),
+        }
+        self
+    }
+
+    fn remove(&self, env: &Env) {
+        match self {
+            DataKeyRegulated::Regulator => storage::Instance::remove(env, self),
+            _ => storage::Persistent::remove(env, self),
+        }
+    }
+}
+
+/// Value of a typed on-chain attribute
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AttributeValue {
+    String(String),
+    U32(u32),
+    I128(i128),
+}
+
+#[contracttype]
+pub enum DataKeyAttributes {
+    MetadataRole, // instance
+    Collection,   // instance
+    Token(u32),   // persistent
+}
+impl storage::Storage for DataKeyAttributes {
+    fn get<V: TryFromVal<Env, Val>>(&self, env: &Env) -> Option<V> {
+        match self {
+            DataKeyAttributes::Token(_) => storage::Persistent::get(env, self),
+            _ => storage::Instance::get(env, self),
+        }
+    }
+
+    fn set<V: IntoVal<Env, Val>>(&self, env: &Env, val: &V) {
+        match self {
+            DataKeyAttributes::Token(_) => storage::Persistent::set(env, self, val),
+            _ => storage::Instance::set(env, self, val),
+        }
+    }
+
+    fn has(&self, env: &Env) -> bool {
+        match self {
+            DataKeyAttributes::Token(_) => storage::Persistent::has(env, self),
+            _ => storage::Instance::has(env, self),
+        }
+    }
+
+    fn extend(&self, env: &Env, min_ledger_to_live: u32) -> &Self {
+        match self {
+            DataKeyAttributes::Token(_) => {
+                storage::Persistent::extend(env, self, min_ledger_to_live)
+            }
+            _ => storage::Instance::extend(env, min_ledger_to_live),
+        }
+        self
+    }
+
+    fn remove(&self, env: &Env) {
+        match self {
+            DataKeyAttributes::Token(_) => storage::Persistent::remove(env, self),
+            _ => storage::Instance::remove(env, self),
+        }
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Recipient {
+    Anyone,
+    Account(Address),
+}
+
+/// Off-chain authorization to mint a token, signed by the voucher signer
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MintVoucher {
+    pub recipient: Recipient,
+    /// The next free id with `None`
+    pub token_id: Option<u32>,
+    /// Uri of the token, unless empty
+    pub uri: String,
+    /// Paid to the admin in `asset`
+    pub price: i128,
+    pub asset: Address,
+    pub expiration_ledger: u32,
+    /// Tells apart vouchers with the same terms
+    pub nonce: u64,
+}
+
+#[contracttype]
+pub enum DataKeyVoucher {
+    Signer,               // instance
+    Redeemed(BytesN<32>), // persistent
+}
+impl storage::Storage for DataKeyVoucher {
+    fn get<V: TryFromVal<Env, Val>>(&self, env: &Env) -> Option<V> {
+        match self {
+            DataKeyVoucher::Signer => storage::Instance::get(env, self),
+            _ => storage::Persistent::get(env, self),
+        }
+    }
+
+    fn set<V: IntoVal<Env, Val>>(&self, env: &Env, val: &V) {
+        match self {
+            DataKeyVoucher::Signer => storage::Instance::set(env, self, val),
+            _ => storage::Persistent::set(env, self, val),
+        }
+    }
+
+    fn has(&self, env: &Env) -> bool {
+        match self {
+            DataKeyVoucher::Signer => storage::Instance::has(env, self),
+            _ => storage::Persistent::has(env, self),
+        }
+    }
+
+    fn extend(&self, env: &Env, min_ledger_to_live: u32) -> &Self {
+        match self {
+            DataKeyVoucher::Signer => storage::Instance::extend(env, min_ledger_to_live),
+            _ => storage::Persistent::extend(env, self, min_ledger_to_live),
+        }
+        self
+    }
+
+    fn remove(&self, env: &Env) {
+        match self {
+            DataKeyVoucher::Signer => storage::Instance::remove(env, self),
+            _ => storage::Persistent::remove(env, self),
+        }
+    }
+}
+
+#[contracttype]
+pub enum DataKeyDelegation {
+    Registry, // instance
+}
+impl storage::Storage for DataKeyDelegation {
     fn get<V: TryFromVal<Env, Val>>(&self, env: &Env) -> Option<V> {
         storage::Instance::get(env, self)
     }
@@ -140,6 +669,80 @@ impl storage::Storage for DataKeyEnumerable {
     }
 }
 
+/// Outcome of one `airdrop` call
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AirdropSummary {
+    pub minted: u32,
+    /// Entries whose token id was already owned by the recipient
+    pub skipped: u32,
+    /// Entries whose token id was owned by another address
+    pub conflicts: u32,
+    /// Index of the first entry left for the next call, the length of the list once done
+    pub next: u32,
+}
+
+/// An ownership change, mints come from the collection address
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProvenanceEntry {
+    pub from: Address,
+    pub to: Address,
+    pub ledger: u32,
+}
+
+/// Ring buffer of the latest ownership changes of a token
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProvenanceLog {
+    /// Index of the oldest entry once the buffer is full
+    pub head: u32,
+    pub entries: Vec<ProvenanceEntry>,
+}
+
+#[contracttype]
+pub enum DataKeyProvenance {
+    Length,   // instance
+    Log(u32), // persistent
+}
+impl storage::Storage for DataKeyProvenance {
+    fn get<V: TryFromVal<Env, Val>>(&self, env: &Env) -> Option<V> {
+        match self {
+            DataKeyProvenance::Length => storage::Instance::get(env, self),
+            _ => storage::Persistent::get(env, self),
+        }
+    }
+
+    fn set<V: IntoVal<Env, Val>>(&self, env: &Env, val: &V) {
+        match self {
+            DataKeyProvenance::Length => storage::Instance::set(env, self, val),
+            _ => storage::Persistent::set(env, self, val),
+        }
+    }
+
+    fn has(&self, env: &Env) -> bool {
+        match self {
+            DataKeyProvenance::Length => storage::Instance::has(env, self),
+            _ => storage::Persistent::has(env, self),
+        }
+    }
+
+    fn extend(&self, env: &Env, min_ledger_to_live: u32) -> &Self {
+        match self {
+            DataKeyProvenance::Length => storage::Instance::extend(env, min_ledger_to_live),
+            _ => storage::Persistent::extend(env, self, min_ledger_to_live),
+        }
+        self
+    }
+
+    fn remove(&self, env: &Env) {
+        match self {
+            DataKeyProvenance::Length => storage::Instance::remove(env, self),
+            _ => storage::Persistent::remove(env, self),
+        }
+    }
+}
+
 #[contracterror]
 #[derive(Copy, Clone, Debug)]
 pub enum Error {
@@ -147,6 +750,24 @@ pub enum Error {
     NotNFT = 1,
     NotAuthorized = 2,
     OutOfBounds = 4,
+    TokenLocked = 5,
+    NotLocked = 6,
+    NonTransferable = 7,
+    FutureLookup = 8,
+    UriTooLong = 9,
+    MetadataTooLarge = 10,
+    InvalidExpiration = 11,
+    TokenNested = 12,
+    NotNested = 13,
+    NestingLoop = 14,
+    HasChildren = 15,
+    OperatorNotAllowed = 16,
+    Frozen = 17,
+    TooManyAttributes = 18,
+    MetadataFrozen = 19,
+    VoucherExpired = 20,
+    VoucherRedeemed = 21,
+    AlreadyMinted = 22,
 }
 
 pub enum Event {
@@ -154,6 +775,34 @@ pub enum Event {
     Transfer,
     Approve,
     Burn,
+    Locked,
+    Unlocked,
+    UpdateUser,
+    ConsecutiveMint,
+    DelegateChanged,
+    DelegateVotesChanged,
+    MetadataUpdate,
+    BatchMetadataUpdate,
+    ContractURIUpdated,
+    ApproveForAll,
+    RevokeAllOperators,
+    ReceivedChild,
+    TransferChild,
+    OperatorFilterUpdated,
+    OperatorAllowed,
+    OperatorDenied,
+    RegulatorChanged,
+    TokenFrozen,
+    AccountFrozen,
+    Clawback,
+    MetadataRoleChanged,
+    AttributeUpdated,
+    PermanentURI,
+    VoucherSignerChanged,
+    VoucherRedeemed,
+    DelegationRegistryChanged,
+    ProvenanceLengthChanged,
+    Airdrop,
 }
 impl Event {
     fn name(&self) -> &'static str {
@@ -162,6 +811,34 @@ impl Event {
             Event::Transfer => stringify!(Transfer),
             Event::Approve => stringify!(Approve),
             Event::Burn => stringify!(Burn),
+            Event::Locked => stringify!(Locked),
+            Event::Unlocked => stringify!(Unlocked),
+            Event::UpdateUser => stringify!(UpdateUser),
+            Event::ConsecutiveMint => stringify!(ConsecutiveMint),
+            Event::DelegateChanged => stringify!(DelegateChanged),
+            Event::DelegateVotesChanged => stringify!(DelegateVotesChanged),
+            Event::MetadataUpdate => stringify!(MetadataUpdate),
+            Event::BatchMetadataUpdate => stringify!(BatchMetadataUpdate),
+            Event::ContractURIUpdated => stringify!(ContractURIUpdated),
+            Event::ApproveForAll => stringify!(ApproveForAll),
+            Event::RevokeAllOperators => stringify!(RevokeAllOperators),
+            Event::ReceivedChild => stringify!(ReceivedChild),
+            Event::TransferChild => stringify!(TransferChild),
+            Event::OperatorFilterUpdated => stringify!(OperatorFilterUpdated),
+            Event::OperatorAllowed => stringify!(OperatorAllowed),
+            Event::OperatorDenied => stringify!(OperatorDenied),
+            Event::RegulatorChanged => stringify!(RegulatorChanged),
+            Event::TokenFrozen => stringify!(TokenFrozen),
+            Event::AccountFrozen => stringify!(AccountFrozen),
+            Event::Clawback => stringify!(Clawback),
+            Event::MetadataRoleChanged => stringify!(MetadataRoleChanged),
+            Event::AttributeUpdated => stringify!(AttributeUpdated),
+            Event::PermanentURI => stringify!(PermanentURI),
+            Event::VoucherSignerChanged => stringify!(VoucherSignerChanged),
+            Event::VoucherRedeemed => stringify!(VoucherRedeemed),
+            Event::DelegationRegistryChanged => stringify!(DelegationRegistryChanged),
+            Event::ProvenanceLengthChanged => stringify!(ProvenanceLengthChanged),
+            Event::Airdrop => stringify!(Airdrop),
         }
     }
     pub fn publish<D>(&self, env: &Env, value: D)
@@ -0,0 +1,129 @@
+use crate::{Checkpoint, DataKeyVotes, Error, Event};
+use soroban_sdk::{panic_with_error, Address, Env};
+use storage::Storage;
+
+/// Move `amount` voting units from the delegate of `from` to the delegate of `to`.
+///
+/// `None` stands for a mint or a burn, in which case the total supply checkpoints
+/// are updated as well.
+pub fn move_voting_units(env: &Env, from: Option<&Address>, to: Option<&Address>, amount: u32) {
+    match (from, to) {
+        (None, Some(_)) => {
+            push(env, History::Total, |v| v + amount);
+        }
+        (Some(_), None) => {
+            push(env, History::Total, |v| v - amount);
+        }
+        _ => {}
+    }
+    let from_delegate = from.and_then(|a| delegates(env, a));
+    let to_delegate = to.and_then(|a| delegates(env, a));
+    move_delegate_votes(env, from_delegate, to_delegate, amount);
+}
+
+/// Move votes from one delegate to another
+pub fn move_delegate_votes(env: &Env, from: Option<Address>, to: Option<Address>, amount: u32) {
+    if from == to || amount == 0 {
+        return;
+    }
+    if let Some(from) = from {
+        let (previous, new) = push(env, History::Account(from.clone()), |v| v - amount);
+        Event::DelegateVotesChanged.publish(env, (from, previous, new));
+    }
+    if let Some(to) = to {
+        let (previous, new) = push(env, History::Account(to.clone()), |v| v + amount);
+        Event::DelegateVotesChanged.publish(env, (to, previous, new));
+    }
+}
+
+pub fn delegates(env: &Env, account: &Address) -> Option<Address> {
+    DataKeyVotes::Delegate(account.clone()).get(env)
+}
+
+/// A checkpoint history, each checkpoint is stored on its own key next to
+/// their count, so an update touches a constant number of entries
+pub enum History {
+    Account(Address),
+    Total,
+}
+impl History {
+    fn count_key(&self) -> DataKeyVotes {
+        match self {
+            History::Account(account) => DataKeyVotes::CheckpointCount(account.clone()),
+            History::Total => DataKeyVotes::TotalCheckpointCount,
+        }
+    }
+
+    fn key(&self, index: u32) -> DataKeyVotes {
+        match self {
+            History::Account(account) => DataKeyVotes::Checkpoint(account.clone(), index),
+            History::Total => DataKeyVotes::TotalCheckpoint(index),
+        }
+    }
+
+    fn len(&self, env: &Env) -> u32 {
+        self.count_key().get(env).unwrap_or(0)
+    }
+
+    fn get(&self, env: &Env, index: u32) -> Checkpoint {
+        self.key(index).get(env).unwrap()
+    }
+}
+
+/// Latest value of a checkpoint history
+pub fn latest(env: &Env, history: &History) -> u32 {
+    match history.len(env) {
+        0 => 0,
+        len => history.get(env, len - 1).votes,
+    }
+}
+
+/// Value of a checkpoint history at the end of a past ledger
+pub fn past(env: &Env, history: &History, ledger: u32) -> u32 {
+    if ledger >= env.ledger().sequence() {
+        panic_with_error!(env, Error::FutureLookup);
+    }
+
+    // Find the first checkpoint recorded after the ledger, reading log(n) entries
+    let mut low = 0;
+    let mut high = history.len(env);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if history.get(env, mid).ledger > ledger {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    if high == 0 {
+        0
+    } else {
+        history.get(env, high - 1).votes
+    }
+}
+
+/// Apply `op` to the latest value and record it for the current ledger
+fn push(env: &Env, history: History, op: impl Fn(u32) -> u32) -> (u32, u32) {
+    let len = history.len(env);
+    let last = match len {
+        0 => None,
+        len => Some(history.get(env, len - 1)),
+    };
+    let ledger = env.ledger().sequence();
+    let previous = last.as_ref().map(|c| c.votes).unwrap_or(0);
+    let new = op(previous);
+
+    // Only keep one checkpoint per ledger
+    match last {
+        Some(last) if last.ledger == ledger => history
+            .key(len - 1)
+            .set(env, &Checkpoint { ledger, votes: new }),
+        _ => {
+            history
+                .key(len)
+                .set(env, &Checkpoint { ledger, votes: new });
+            history.count_key().set(env, &(len + 1));
+        }
+    }
+    (previous, new)
+}
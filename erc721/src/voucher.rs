@@ -0,0 +1,47 @@
+//! Lazy minting: the voucher signer authorizes mints off-chain with ed25519
+//! signatures, buyers redeem the vouchers and pay for the mint themselves.
+//!
+//! The signed message is the XDR of `(contract address, voucher)`, a voucher
+//! can't be replayed on another collection. Redeemed vouchers are recorded by
+//! the SHA-256 of that message.
+use crate::{DataKeyVoucher, Error, MintVoucher, Recipient};
+use soroban_sdk::{panic_with_error, xdr::ToXdr, Address, Bytes, BytesN, Env};
+use storage::Storage;
+
+pub fn signer(env: &Env) -> Option<BytesN<32>> {
+    DataKeyVoucher::Signer.get(env)
+}
+
+/// What the signer signs for `voucher`
+pub fn message(env: &Env, voucher: &MintVoucher) -> Bytes {
+    (env.current_contract_address(), voucher.clone()).to_xdr(env)
+}
+
+/// Check `voucher` can be redeemed by `redeemer` and record it, return its digest
+pub fn consume(
+    env: &Env,
+    redeemer: &Address,
+    voucher: &MintVoucher,
+    signature: &BytesN<64>,
+) -> BytesN<32> {
+    if let Recipient::Account(recipient) = &voucher.recipient {
+        if recipient != redeemer {
+            panic_with_error!(env, Error::NotAuthorized);
+        }
+    }
+    if env.ledger().sequence() > voucher.expiration_ledger {
+        panic_with_error!(env, Error::VoucherExpired);
+    }
+    let signer = signer(env).unwrap_or_else(|| panic_with_error!(env, Error::NotAuthorized));
+    let message = message(env, voucher);
+    // Fails the call on a bad signature
+    env.crypto().ed25519_verify(&signer, &message, signature);
+
+    let digest = env.crypto().sha256(&message);
+    let key = DataKeyVoucher::Redeemed(digest.clone());
+    if key.has(env) {
+        panic_with_error!(env, Error::VoucherRedeemed);
+    }
+    key.set(env, &true);
+    digest
+}
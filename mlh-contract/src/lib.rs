@@ -1,8 +1,12 @@
 #![no_std]
 
-use erc721::{DataKey, ERC721Metadata, Error, ERC721};
+use erc721::{
+    AirdropSummary, DataKey, ERC721Introspection, ERC721Metadata, Error, ERC721, MAX_WRITE_ENTRIES,
+    MINT_WRITES,
+};
 use soroban_sdk::{
-    contract, contractimpl, panic_with_error, token, Address, Bytes, BytesN, Env, String,
+    contract, contractimpl, panic_with_error, token, Address, Bytes, BytesN, Env, String, Symbol,
+    Vec,
 };
 use storage::Storage;
 mod types;
@@ -20,6 +24,10 @@ pub const MAX_SUPPLY: u32 = 0xfff;
 #[cfg(not(test))]
 pub const MAX_XY: (u32, u32) = (0x7f, 0x1f);
 
+/// Pixels given by one `airdrop` call: each writes its two `Coords` entries on
+/// top of the erc721 mint, the contract instance is written once.
+pub const MAX_AIRDROP_PIXELS: u32 = (MAX_WRITE_ENTRIES - 1) / (MINT_WRITES + 2);
+
 #[contract]
 pub struct Million;
 
@@ -96,6 +104,51 @@ impl Million {
         Ok(token_id)
     }
 
+    /// Give each `(recipient, x, y)` pixel for free, admin only.
+    ///
+    /// Free pixels get the next token ids, the taken ones keep theirs and are
+    /// reported by the erc721 airdrop as skipped or conflicts. At most
+    /// `MAX_AIRDROP_PIXELS` entries are handled per call, the rest is resumed
+    /// from `summary.next`.
+    pub fn airdrop(env: Env, recipients: Vec<(Address, u32, u32)>) -> AirdropSummary {
+        erc721::get_admin(&env).require_auth();
+
+        let mut token_id: u32 = MillionDataKey::TokenId.get(&env).unwrap_or(0);
+        let mut tokens = Vec::new(&env);
+        for (to, x, y) in recipients.iter().take(MAX_AIRDROP_PIXELS as usize) {
+            if x > MAX_XY.0 || y > MAX_XY.1 {
+                panic!("X or Y too big")
+            }
+            let id = match Coords::Token(x, y).get::<u32>(&env) {
+                Some(id) => id,
+                None => {
+                    if token_id > MAX_SUPPLY {
+                        panic!("Exhausted")
+                    }
+                    let id = token_id;
+                    token_id += 1;
+                    Coords::Token(x, y).set(&env, &id);
+                    Coords::Xy(id).set(&env, &(x, y));
+                    Coords::Token(x, y).extend(&env, MAX_BUMP);
+                    Coords::Xy(id).extend(&env, MAX_BUMP);
+                    id
+                }
+            };
+            tokens.push_back((to, id));
+        }
+        MillionDataKey::TokenId.set::<u32>(&env, &token_id);
+
+        let summary = erc721::ERC721Contract::airdrop(env.clone(), tokens.clone());
+        for (to, id) in tokens.iter() {
+            // Pixels owned by someone else are left as they are
+            if erc721::ERC721Contract::owner_of(env.clone(), id) == to {
+                DataKey::Balance(to).extend(&env, MAX_BUMP);
+                DataKey::TokenOwner(id).extend(&env, MAX_BUMP);
+            }
+        }
+        summary
+    }
+
     pub fn balance_of(env: Env, owner: Address) -> u32 {
         erc721::ERC721Contract::balance_of(env, owner)
     }
@@ -140,6 +193,18 @@ impl Million {
         erc721::ERC721Contract::is_approval_for_all(env, owner, operator)
     }
 
+    pub fn approval_expiration(env: Env, token_id: u32) -> Option<u32> {
+        erc721::ERC721Contract::approval_expiration(env, token_id)
+    }
+
+    pub fn operator_expiration(env: Env, owner: Address, operator: Address) -> Option<u32> {
+        erc721::ERC721Contract::operator_expiration(env, owner, operator)
+    }
+
+    pub fn revoke_all_operators(env: Env, owner: Address) {
+        erc721::ERC721Contract::revoke_all_operators(env, owner)
+    }
+
     pub fn name(env: Env) -> String {
         erc721::ERC721Contract::name(env)
     }
@@ -179,9 +244,11 @@ impl Million {
     }
 
     pub fn owner_of(env: Env, token_id: u32) -> Address {
-        erc721::DataKey::TokenOwner(token_id)
-            .get(&env)
-            .unwrap_or_else(|| panic!("token_id does not exist"))
+        erc721::ERC721Contract::owner_of(env, token_id)
+    }
+
+    pub fn supports_interface(env: Env, interface_id: Symbol) -> bool {
+        erc721::ERC721Contract::supports_interface(env, interface_id)
     }
 
     pub fn coords(env: Env, token_id: u32) -> Option<(u32, u32)> {
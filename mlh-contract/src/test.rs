@@ -68,6 +68,63 @@ fn mint() {
     client.token_uri(&1).copy_into_slice(sl);
     assert_eq!(sl, "http://localhost:3000/test/0x001.json".as_bytes());
 }
+#[test]
+fn airdrop() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Million);
+    let client = MillionClient::new(&env, &contract_id);
+
+    let asset_admin = Address::generate(&env);
+    let native_addr = env.register_stellar_asset_contract(asset_admin.clone());
+    let asset_client_admin = token::StellarAssetClient::new(&env, &native_addr);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &native_addr, &100);
+
+    // A pixel bought before the airdrop
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    asset_client_admin.mock_all_auths().mint(&user2, &100);
+    client.mock_all_auths().mint(&1, &0, &user2);
+
+    let recipients = soroban_sdk::vec![
+        &env,
+        (user1.clone(), 0, 0),
+        (user1.clone(), 1, 0),
+        (user2.clone(), 2, 0),
+    ];
+    let summary = client.mock_all_auths().airdrop(&recipients);
+    assert_eq!(
+        summary,
+        AirdropSummary {
+            minted: 2,
+            skipped: 0,
+            conflicts: 1,
+            next: 3,
+        }
+    );
+    assert_eq!(client.owner_of(&0), user2);
+    assert_eq!(client.coords(&1), Some((0, 0)));
+    assert_eq!(client.owner_of(&1), user1);
+    assert_eq!(client.coords(&2), Some((2, 0)));
+    assert_eq!(client.balance_of(&user1), 1);
+
+    // Sent again, the pixels are not given twice
+    let summary = client.mock_all_auths().airdrop(&recipients);
+    assert_eq!(summary.minted, 0);
+    assert_eq!(summary.skipped, 2);
+    assert_eq!(client.total_supply(), 3);
+
+    // A longer list is cut to what one transaction can write
+    let mut recipients = soroban_sdk::Vec::new(&env);
+    for x in 0..MAX_AIRDROP_PIXELS + 1 {
+        recipients.push_back((user1.clone(), x, 1));
+    }
+    let summary = client.mock_all_auths().airdrop(&recipients);
+    assert_eq!(summary.minted, MAX_AIRDROP_PIXELS);
+    assert_eq!(summary.next, MAX_AIRDROP_PIXELS);
+}
+
 #[test]
 fn mint_all() {
     let max = crate::MAX_SUPPLY + 1;
@@ -116,3 +173,29 @@ fn mint_all() {
     }
     //assert_eq!(result, Err(Ok(MillionError::Exhausted)));
 }
+
+#[test]
+fn conformance() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Million);
+    let client = MillionClient::new(&env, &contract_id);
+
+    let asset_admin = Address::generate(&env);
+    let native_addr = env.register_stellar_asset_contract(asset_admin.clone());
+    let asset_client_admin = token::StellarAssetClient::new(&env, &native_addr);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &native_addr, &100);
+
+    // Each token is minted on the next free pixel, paid by its owner
+    let next_pixel = core::cell::Cell::new(0);
+    let mint = |to: &Address| {
+        let i = next_pixel.get();
+        next_pixel.set(i + 1);
+        asset_client_admin.mock_all_auths().mint(to, &100);
+        client
+            .mock_all_auths()
+            .mint(&(i % (MAX_XY.0 + 1)), &(i / (MAX_XY.0 + 1)), to)
+    };
+    erc721_testkit::Suite::new(&env, &contract_id, &mint).run();
+}
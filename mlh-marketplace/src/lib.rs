@@ -5,7 +5,7 @@ use soroban_sdk::{
 };
 
 mod stub {
-    use soroban_sdk::{contract, contractimpl, Address, Env};
+    use soroban_sdk::{contract, contractimpl, Address, Env, Symbol};
 
     #[contract]
     pub struct Erc721;
@@ -33,6 +33,10 @@ mod stub {
         pub fn balance_of(_env: Env, _owner: Address) -> u32 {
             0
         }
+
+        pub fn supports_interface(_env: Env, _interface_id: Symbol) -> bool {
+            false
+        }
     }
 }
 #[contracttype]
@@ -91,6 +95,10 @@ impl MarketPlace {
         }
 
         let erc721 = stub::Erc721Client::new(&env, &nft.contract_id);
+        // Only collections declaring the ERC721 interface can be listed
+        if !erc721.supports_interface(&Symbol::new(&env, erc721::introspection::ERC721)) {
+            panic!("Not an ERC721 collection");
+        }
         // Force the marketplace to be approved on the token
         // This also confirm that the token is owned and exists
         erc721.approve(
@@ -153,12 +161,4 @@ impl MarketPlace {
 }
 
 #[cfg(test)]
-mod tests {
-    use soroban_sdk::{xdr::Asset, Address, BytesN, Env};
-    use stellar_strkey::Strkey;
-
-    fn test() {
-        //
-        let env = Env::default();
-    }
-}
+mod test;
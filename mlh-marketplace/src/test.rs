@@ -0,0 +1,94 @@
+#![cfg(test)]
+
+use super::*;
+use erc721::{ERC721Introspection, ERC721};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::String;
+
+#[contract]
+pub struct Collection;
+
+#[contractimpl]
+impl Collection {
+    pub fn initialize(env: Env, admin: Address) {
+        let name = String::from_str(&env, "Collection");
+        let sym = String::from_str(&env, "COL");
+        erc721::ERC721Contract::initialize(env, admin, name, sym);
+    }
+    pub fn mint(env: Env, to: Address, token_id: u32) {
+        erc721::ERC721Contract::mint(env, to, token_id)
+    }
+    pub fn approve(
+        env: Env,
+        caller: Address,
+        operator: Option<Address>,
+        token_id: u32,
+        expiration_ledger: u32,
+    ) {
+        erc721::ERC721Contract::approve(env, caller, operator, token_id, expiration_ledger)
+    }
+    pub fn get_approved(env: Env, token_id: u32) -> Option<Address> {
+        erc721::ERC721Contract::get_approved(env, token_id)
+    }
+    pub fn balance_of(env: Env, owner: Address) -> u32 {
+        erc721::ERC721Contract::balance_of(env, owner)
+    }
+    pub fn supports_interface(env: Env, interface_id: Symbol) -> bool {
+        erc721::ERC721Contract::supports_interface(env, interface_id)
+    }
+}
+
+/// A collection and a marketplace listing it, `seller` owns token 1
+fn setup(env: &Env) -> (Address, Address, Address) {
+    env.mock_all_auths();
+    let seller = Address::generate(env);
+    let collection = env.register_contract(None, Collection);
+    let client = CollectionClient::new(env, &collection);
+    client.initialize(&Address::generate(env));
+    client.mint(&seller, &1);
+
+    let marketplace = env.register_contract(None, MarketPlace);
+    MarketPlaceClient::new(env, &marketplace).initialize(&collection);
+    (collection, marketplace, seller)
+}
+
+fn price(env: &Env) -> Price {
+    Price {
+        amount: 100,
+        asset: Address::generate(env),
+    }
+}
+
+#[test]
+fn sell_erc721() {
+    let env = Env::default();
+    let (collection, marketplace, seller) = setup(&env);
+
+    let nft = Nft {
+        contract_id: collection.clone(),
+        token_id: 1,
+    };
+    MarketPlaceClient::new(&env, &marketplace).sell(&nft, &seller, &seller, &price(&env), &1000);
+    assert_eq!(
+        CollectionClient::new(&env, &collection).get_approved(&1),
+        Some(marketplace)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Not an ERC721 collection")]
+fn sell_rejects_other_contracts() {
+    let env = Env::default();
+    let (_, marketplace, seller) = setup(&env);
+
+    // The stub doesn't declare the erc721 interface. A contract panic can't
+    // unwind through the client, the listing is run as the marketplace.
+    let nft = Nft {
+        contract_id: env.register_contract(None, stub::Erc721),
+        token_id: 1,
+    };
+    let price = price(&env);
+    env.as_contract(&marketplace, || {
+        MarketPlace::sell(env.clone(), nft, seller.clone(), seller, price, 1000)
+    });
+}
@@ -0,0 +1,241 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractclient, contractimpl, panic_with_error, token, Address, Env, Vec,
+};
+use storage::Storage;
+mod types;
+pub use crate::types::*;
+
+const MAX_BUMP: u32 = 100_000;
+
+/// Parcels staked at once by an owner, `stakes_of` reads them all
+pub const MAX_STAKES: u32 = 100;
+
+/// What is called on the staked collection
+#[contractclient(name = "CollectionClient")]
+pub trait Collection {
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, token_id: u32);
+    fn owner_of(env: Env, token_id: u32) -> Address;
+}
+
+#[contract]
+pub struct Staking;
+
+/// Holders lock parcels of an erc721 collection here and earn `rate` units of
+/// the reward asset per parcel and ledger. The owner approves this contract
+/// for the parcel, which is then held until it is unstaked. Rewards are paid
+/// from the contract balance, topped up with `fund`.
+#[contractimpl]
+impl Staking {
+    pub fn initialize(env: Env, admin: Address, collection: Address, asset: Address, rate: i128) {
+        if StakingDataKey::Admin.has(&env) {
+            panic!("Already initialized")
+        }
+        if rate < 0 {
+            panic_with_error!(&env, Error::InvalidRate);
+        }
+        StakingDataKey::Admin
+            .extend(&env, MAX_BUMP)
+            .set::<Address>(&env, &admin);
+        StakingDataKey::Collection.set::<Address>(&env, &collection);
+        StakingDataKey::Asset.set::<Address>(&env, &asset);
+        StakingDataKey::Rate.set::<i128>(&env, &rate);
+        StakingDataKey::Index.set(
+            &env,
+            &RewardIndex {
+                index: 0,
+                ledger: env.ledger().sequence(),
+            },
+        );
+    }
+
+    /// Change the reward per parcel and ledger, what was accrued at the
+    /// previous rate is kept
+    pub fn set_rate(env: Env, rate: i128) {
+        admin(&env).require_auth();
+        if rate < 0 {
+            panic_with_error!(&env, Error::InvalidRate);
+        }
+        update_index(&env);
+        StakingDataKey::Rate
+            .extend(&env, MAX_BUMP)
+            .set::<i128>(&env, &rate);
+        Event::RateChanged.publish(&env, rate);
+    }
+
+    pub fn rate(env: Env) -> i128 {
+        rate(&env)
+    }
+
+    /// Send `amount` of the reward asset to the contract
+    pub fn fund(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+        token::Client::new(&env, &asset(&env)).transfer(
+            &from,
+            &env.current_contract_address(),
+            &amount,
+        );
+    }
+
+    /// Lock `token_id`, `owner` must have approved this contract for it
+    pub fn stake(env: Env, owner: Address, token_id: u32) {
+        owner.require_auth();
+        let mut staked = stakes_of(&env, &owner);
+        if staked.len() >= MAX_STAKES {
+            panic_with_error!(&env, Error::TooManyStakes);
+        }
+        let this = env.current_contract_address();
+        CollectionClient::new(&env, &collection(&env))
+            .transfer_from(&this, &owner, &this, &token_id);
+
+        let index = update_index(&env);
+        StakeDataKey::Stake(token_id).set(
+            &env,
+            &Stake {
+                owner: owner.clone(),
+                start_ledger: env.ledger().sequence(),
+                paid_index: index,
+            },
+        );
+        staked.push_back(token_id);
+        StakeDataKey::Staked(owner.clone()).set(&env, &staked);
+        Event::Staked.publish(&env, (owner, token_id));
+    }
+
+    /// Pay the rewards of `token_id` accrued since it was staked or last claimed
+    pub fn claim(env: Env, owner: Address, token_id: u32) -> i128 {
+        owner.require_auth();
+        let mut stake = owned_stake(&env, &owner, token_id);
+        let amount = pay(&env, token_id, &mut stake);
+        StakeDataKey::Stake(token_id).set(&env, &stake);
+        amount
+    }
+
+    /// Give `token_id` back to its owner and pay the pending rewards
+    pub fn unstake(env: Env, owner: Address, token_id: u32) -> i128 {
+        owner.require_auth();
+        let mut stake = owned_stake(&env, &owner, token_id);
+        release(&env, &owner, token_id);
+        let amount = pay(&env, token_id, &mut stake);
+        Event::Unstaked.publish(&env, (owner, token_id, stake.start_ledger));
+        amount
+    }
+
+    /// Give `token_id` back to its owner without its rewards, when the contract
+    /// lacks the funds to pay them
+    pub fn emergency_unstake(env: Env, owner: Address, token_id: u32) {
+        owner.require_auth();
+        let stake = owned_stake(&env, &owner, token_id);
+        release(&env, &owner, token_id);
+        Event::EmergencyUnstaked.publish(&env, (owner, token_id, stake.start_ledger));
+    }
+
+    pub fn pending_rewards(env: Env, token_id: u32) -> i128 {
+        match StakeDataKey::Stake(token_id).get::<Stake>(&env) {
+            Some(stake) => current_index(&env).index - stake.paid_index,
+            None => 0,
+        }
+    }
+
+    pub fn stake_of(env: Env, token_id: u32) -> Option<Stake> {
+        StakeDataKey::Stake(token_id).get(&env)
+    }
+
+    pub fn stakes_of(env: Env, owner: Address) -> Vec<u32> {
+        stakes_of(&env, &owner)
+    }
+}
+
+fn admin(env: &Env) -> Address {
+    StakingDataKey::Admin
+        .get(env)
+        .unwrap_or_else(|| panic_with_error!(env, Error::NotInitialized))
+}
+
+fn collection(env: &Env) -> Address {
+    StakingDataKey::Collection
+        .get(env)
+        .unwrap_or_else(|| panic_with_error!(env, Error::NotInitialized))
+}
+
+fn asset(env: &Env) -> Address {
+    StakingDataKey::Asset
+        .get(env)
+        .unwrap_or_else(|| panic_with_error!(env, Error::NotInitialized))
+}
+
+fn rate(env: &Env) -> i128 {
+    StakingDataKey::Rate.get(env).unwrap_or(0)
+}
+
+fn stakes_of(env: &Env, owner: &Address) -> Vec<u32> {
+    StakeDataKey::Staked(owner.clone())
+        .get(env)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn owned_stake(env: &Env, owner: &Address, token_id: u32) -> Stake {
+    let stake: Stake = StakeDataKey::Stake(token_id)
+        .get(env)
+        .unwrap_or_else(|| panic_with_error!(env, Error::NotStaked));
+    if stake.owner != *owner {
+        panic_with_error!(env, Error::NotOwner);
+    }
+    stake
+}
+
+/// Drop the stake of `token_id` and transfer it back to `owner`
+fn release(env: &Env, owner: &Address, token_id: u32) {
+    StakeDataKey::Stake(token_id).remove(env);
+    let key = StakeDataKey::Staked(owner.clone());
+    let mut staked = stakes_of(env, owner);
+    if let Some(index) = staked.first_index_of(token_id) {
+        staked.remove(index);
+    }
+    if staked.is_empty() {
+        key.remove(env);
+    } else {
+        key.set(env, &staked);
+    }
+
+    let this = env.current_contract_address();
+    CollectionClient::new(env, &collection(env)).transfer_from(&this, &this, owner, &token_id);
+}
+
+/// Reward per parcel accrued up to the current ledger
+fn current_index(env: &Env) -> RewardIndex {
+    let last: RewardIndex = StakingDataKey::Index
+        .get(env)
+        .unwrap_or_else(|| panic_with_error!(env, Error::NotInitialized));
+    let ledger = env.ledger().sequence();
+    RewardIndex {
+        index: last.index + rate(env) * (ledger - last.ledger) as i128,
+        ledger,
+    }
+}
+
+fn update_index(env: &Env) -> i128 {
+    let current = current_index(env);
+    StakingDataKey::Index.set(env, &current);
+    current.index
+}
+
+/// Transfer what `stake` accrued to its owner and move its index forward
+fn pay(env: &Env, token_id: u32, stake: &mut Stake) -> i128 {
+    let index = update_index(env);
+    let amount = index - stake.paid_index;
+    stake.paid_index = index;
+    if amount > 0 {
+        token::Client::new(env, &asset(env)).transfer(
+            &env.current_contract_address(),
+            &stake.owner,
+            &amount,
+        );
+        Event::Claimed.publish(env, (stake.owner.clone(), token_id, amount));
+    }
+    amount
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,119 @@
+#![cfg(test)]
+
+use super::*;
+use erc721::ERC721;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::String;
+
+#[contract]
+pub struct Parcels;
+
+#[contractimpl]
+impl Parcels {
+    pub fn initialize(env: Env, admin: Address) {
+        let name = String::from_str(&env, "Parcel");
+        let sym = String::from_str(&env, "PCL");
+        erc721::ERC721Contract::initialize(env, admin, name, sym);
+    }
+    pub fn mint(env: Env, to: Address, token_id: u32) {
+        erc721::ERC721Contract::mint(env, to, token_id)
+    }
+    pub fn approve(
+        env: Env,
+        caller: Address,
+        operator: Option<Address>,
+        token_id: u32,
+        expiration_ledger: u32,
+    ) {
+        erc721::ERC721Contract::approve(env, caller, operator, token_id, expiration_ledger)
+    }
+    pub fn transfer_from(env: Env, spender: Address, from: Address, to: Address, token_id: u32) {
+        erc721::ERC721Contract::transfer_from(env, spender, from, to, token_id)
+    }
+    pub fn owner_of(env: Env, token_id: u32) -> Address {
+        erc721::ERC721Contract::owner_of(env, token_id)
+    }
+}
+
+#[test]
+fn stake_claim_unstake() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.sequence_number = 100);
+
+    let admin = Address::generate(&env);
+    let holder = Address::generate(&env);
+
+    let parcels_id = env.register_contract(None, Parcels);
+    let parcels = ParcelsClient::new(&env, &parcels_id);
+    parcels.initialize(&admin);
+    parcels.mint(&holder, &1);
+    parcels.mint(&holder, &2);
+
+    let asset = env.register_stellar_asset_contract(admin.clone());
+    token::StellarAssetClient::new(&env, &asset).mint(&admin, &10_000);
+    let reward = token::Client::new(&env, &asset);
+
+    let contract_id = env.register_contract(None, Staking);
+    let client = StakingClient::new(&env, &contract_id);
+    client.initialize(&admin, &parcels_id, &asset, &3);
+    client.fund(&admin, &10_000);
+
+    parcels.approve(&holder, &Some(contract_id.clone()), &1, &1000);
+    client.stake(&holder, &1);
+    assert_eq!(parcels.owner_of(&1), contract_id);
+    assert_eq!(client.stake_of(&1).unwrap().start_ledger, 100);
+
+    env.ledger().with_mut(|l| l.sequence_number = 110);
+    assert_eq!(client.pending_rewards(&1), 30);
+    assert_eq!(client.claim(&holder, &1), 30);
+    assert_eq!(reward.balance(&holder), 30);
+    assert_eq!(client.pending_rewards(&1), 0);
+
+    // Accrued at the previous rate is kept
+    parcels.approve(&holder, &Some(contract_id.clone()), &2, &1000);
+    client.stake(&holder, &2);
+    env.ledger().with_mut(|l| l.sequence_number = 120);
+    client.set_rate(&5);
+    env.ledger().with_mut(|l| l.sequence_number = 130);
+    assert_eq!(client.pending_rewards(&1), 80);
+    assert_eq!(client.pending_rewards(&2), 80);
+    assert_eq!(client.stakes_of(&holder), soroban_sdk::vec![&env, 1, 2]);
+
+    assert_eq!(client.unstake(&holder, &1), 80);
+    assert_eq!(parcels.owner_of(&1), holder);
+    assert_eq!(reward.balance(&holder), 110);
+    assert_eq!(client.stake_of(&1), None);
+    assert_eq!(client.stakes_of(&holder), soroban_sdk::vec![&env, 2]);
+    assert_eq!(client.pending_rewards(&1), 0);
+}
+
+#[test]
+fn emergency_unstake() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let holder = Address::generate(&env);
+    let parcels_id = env.register_contract(None, Parcels);
+    let parcels = ParcelsClient::new(&env, &parcels_id);
+    parcels.initialize(&admin);
+    parcels.mint(&holder, &1);
+
+    // Nothing funded, the rewards can't be paid
+    let asset = env.register_stellar_asset_contract(admin.clone());
+    let contract_id = env.register_contract(None, Staking);
+    let client = StakingClient::new(&env, &contract_id);
+    client.initialize(&admin, &parcels_id, &asset, &3);
+
+    parcels.approve(&holder, &Some(contract_id.clone()), &1, &1000);
+    client.stake(&holder, &1);
+    env.ledger().with_mut(|l| l.sequence_number = 10);
+    assert_eq!(client.pending_rewards(&1), 30);
+
+    client.emergency_unstake(&holder, &1);
+    assert_eq!(parcels.owner_of(&1), holder);
+    assert_eq!(client.stake_of(&1), None);
+    assert_eq!(client.stakes_of(&holder), soroban_sdk::vec![&env]);
+    assert_eq!(token::Client::new(&env, &asset).balance(&holder), 0);
+}
@@ -0,0 +1,112 @@
+use soroban_sdk::{contracterror, contracttype, Address, Env, IntoVal, TryFromVal, Val};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Stake {
+    pub owner: Address,
+    pub start_ledger: u32,
+    /// Reward per parcel accrued when the stake was last paid
+    pub paid_index: i128,
+}
+
+/// Reward per parcel accrued since the contract started, as of `ledger`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardIndex {
+    pub index: i128,
+    pub ledger: u32,
+}
+
+#[contracttype]
+pub enum StakingDataKey {
+    Admin,      // instance
+    Collection, // instance
+    Asset,      // instance
+    Rate,       // instance
+    Index,      // instance
+}
+impl storage::Storage for StakingDataKey {
+    fn get<V: TryFromVal<Env, Val>>(&self, env: &Env) -> Option<V> {
+        storage::Instance::get(env, self)
+    }
+
+    fn set<V: IntoVal<Env, Val>>(&self, env: &Env, val: &V) {
+        storage::Instance::set(env, self, val)
+    }
+
+    fn has(&self, env: &Env) -> bool {
+        storage::Instance::has(env, self)
+    }
+
+    fn extend(&self, env: &Env, min_ledger_to_live: u32) -> &Self {
+        storage::Instance::extend(env, min_ledger_to_live);
+        self
+    }
+
+    fn remove(&self, env: &Env) {
+        storage::Instance::remove(env, self)
+    }
+}
+
+#[contracttype]
+pub enum StakeDataKey {
+    Stake(u32),      // persistent
+    Staked(Address), // persistent
+}
+impl storage::Storage for StakeDataKey {
+    fn get<V: TryFromVal<Env, Val>>(&self, env: &Env) -> Option<V> {
+        storage::Persistent::get(env, self)
+    }
+
+    fn set<V: IntoVal<Env, Val>>(&self, env: &Env, val: &V) {
+        storage::Persistent::set(env, self, val)
+    }
+
+    fn has(&self, env: &Env) -> bool {
+        storage::Persistent::has(env, self)
+    }
+
+    fn extend(&self, env: &Env, min_ledger_to_live: u32) -> &Self {
+        storage::Persistent::extend(env, self, min_ledger_to_live);
+        self
+    }
+
+    fn remove(&self, env: &Env) {
+        storage::Persistent::remove(env, self)
+    }
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug)]
+pub enum Error {
+    NotInitialized = 1,
+    NotStaked = 2,
+    NotOwner = 3,
+    InvalidRate = 4,
+    TooManyStakes = 5,
+}
+
+pub enum Event {
+    Staked,
+    Unstaked,
+    EmergencyUnstaked,
+    Claimed,
+    RateChanged,
+}
+impl Event {
+    fn name(&self) -> &'static str {
+        match self {
+            Event::Staked => stringify!(Staked),
+            Event::Unstaked => stringify!(Unstaked),
+            Event::EmergencyUnstaked => stringify!(EmergencyUnstaked),
+            Event::Claimed => stringify!(Claimed),
+            Event::RateChanged => stringify!(RateChanged),
+        }
+    }
+    pub fn publish<D>(&self, env: &Env, value: D)
+    where
+        D: IntoVal<Env, Val>,
+    {
+        env.events().publish((self.name(),), value);
+    }
+}